@@ -11,6 +11,7 @@ use ac_library::*;
 /* }}} */
 
 
+#[allow(unused_variables)]
 fn main() {
     input! {
         a: usize,
@@ -11,6 +11,7 @@ use ac_library::*;
 /* }}} */
 
 
+#[allow(clippy::needless_range_loop)]
 fn main() {
     input! {
         n: usize, m: usize,
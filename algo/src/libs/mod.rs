@@ -1,15 +0,0 @@
-mod weighted_dsu;
-mod prime;
-mod rolling_hash;
-mod graph;
-mod ahc;
-mod linear_programming;
-mod zobrist;
-mod coord;
-mod ring;
-mod monoid_dsu;
-mod skip_list;
-mod raq;
-mod monoid;
-mod map_monoid;
-mod more_itertools;
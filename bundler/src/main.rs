@@ -0,0 +1,500 @@
+//! Expands the `libs` modules used by a solution into a single submittable file.
+//!
+//! ```text
+//! cargo run -p bundler -- algo/src/a.rs [--libs <dir>] [-o <output>]
+//! ```
+//!
+//! Every `use libs::...` path in the solution names a module declared in `libs/src/lib.rs`.
+//! Those modules, together with the modules they reach through `super::...` paths,
+//! are inlined as `mod libs { pub mod <name> { ... } }` without their `#[cfg(test)]` modules,
+//! so the items a solution uses must be `pub`.
+//! The block replaces a `mod libs;` line if the solution has one,
+//! and otherwise goes right after the `/* imports {{{ */` fold.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+
+/* mask {{{ */
+/// Returns a copy of `src` where comments and the contents of string and char literals are replaced by spaces.
+/// Newlines and byte offsets are preserved, so positions in the result are valid in `src`.
+///
+/// Time complexity is O(n)
+fn mask(src: &str) -> String {
+    let b = src.as_bytes();
+    let n = b.len();
+    let mut res = b.to_vec();
+    let is_ident = |c: u8| c.is_ascii_alphanumeric() || c == b'_';
+    let blank = |res: &mut Vec<u8>, l: usize, r: usize| {
+        for c in res[l..r].iter_mut() {
+            if *c != b'\n' {
+                *c = b' ';
+            }
+        }
+    };
+
+    let mut i = 0;
+    while i < n {
+        if b[i..].starts_with(b"//") {
+            let end = b[i..].iter().position(|&c| c == b'\n').map_or(n, |p| i + p);
+            blank(&mut res, i, end);
+            i = end;
+        } else if b[i..].starts_with(b"/*") {
+            let mut depth = 0;
+            let mut j = i;
+            while j < n {
+                if b[j..].starts_with(b"/*") {
+                    depth += 1;
+                    j += 2;
+                } else if b[j..].starts_with(b"*/") {
+                    depth -= 1;
+                    j += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    j += 1;
+                }
+            }
+            blank(&mut res, i, j);
+            i = j;
+        } else if b[i] == b'r' && (i == 0 || !is_ident(b[i - 1]) || (b[i - 1] == b'b' && (i < 2 || !is_ident(b[i - 2]))))
+            && b[i + 1..].iter().position(|&c| c != b'#').is_some_and(|p| b[i + 1 + p] == b'"')
+        {
+            let hashes = b[i + 1..].iter().take_while(|&&c| c == b'#').count();
+            let start = i + 1 + hashes + 1;
+            let mut closing = vec![b'"'];
+            closing.extend(std::iter::repeat_n(b'#', hashes));
+            let end = (start..n).find(|&j| b[j..].starts_with(&closing)).unwrap_or(n);
+            blank(&mut res, start, end);
+            i = (end + closing.len()).min(n);
+        } else if b[i] == b'"' {
+            let mut j = i + 1;
+            while j < n && b[j] != b'"' {
+                j += if b[j] == b'\\' { 2 } else { 1 };
+            }
+            blank(&mut res, i + 1, j.min(n));
+            i = j + 1;
+        } else if b[i] == b'\'' && i + 1 < n {
+            let end = if b[i + 1] == b'\\' {
+                (i + 3..n).find(|&j| b[j] == b'\'')
+            } else {
+                let len = src[i + 1..].chars().next().map_or(1, |c| c.len_utf8());
+                Some(i + 1 + len).filter(|&j| j < n && b[j] == b'\'')
+            };
+            match end {
+                Some(end) => {
+                    blank(&mut res, i + 1, end);
+                    i = end + 1;
+                }
+                // lifetime
+                None => i += 1,
+            }
+        } else {
+            i += 1;
+        }
+    }
+    String::from_utf8(res).unwrap()
+}
+/* }}} */
+
+
+/* strip_tests {{{ */
+/// Returns `src` without the `#[cfg(test)]` modules.
+///
+/// Time complexity is O(n)
+fn strip_tests(src: &str) -> String {
+    let masked = mask(src);
+    let mut res = String::new();
+    let mut last = 0;
+    let mut from = 0;
+    while let Some(pos) = masked[from..].find("#[cfg(test)]").map(|p| from + p) {
+        let open = match masked[pos..].find('{') {
+            Some(p) => pos + p,
+            None => break,
+        };
+        let mut depth = 0;
+        let mut close = masked.len();
+        for (i, c) in masked[open..].char_indices() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth -= 1,
+                _ => continue,
+            }
+            if depth == 0 {
+                close = open + i + 1;
+                break;
+            }
+        }
+        let begin = masked[..pos].rfind('\n').map_or(0, |p| p + 1);
+        let end = masked[close..].find('\n').map_or(masked.len(), |p| close + p + 1);
+        res.push_str(&src[last..begin]);
+        last = end;
+        from = end;
+    }
+    res.push_str(&src[last..]);
+    res
+}
+/* }}} */
+
+
+/* tokenize {{{ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Ident(String),
+    PathSep,
+    Punct(u8),
+}
+
+/// Splits masked source code into identifiers, `::` and single punctuation characters.
+///
+/// Time complexity is O(n)
+fn tokenize(code: &str) -> Vec<Token> {
+    let b = code.as_bytes();
+    let mut tokens = vec![];
+    let mut i = 0;
+    while i < b.len() {
+        if b[i].is_ascii_alphanumeric() || b[i] == b'_' {
+            let len = b[i..].iter().take_while(|&&c| c.is_ascii_alphanumeric() || c == b'_').count();
+            tokens.push(Token::Ident(code[i..i + len].to_string()));
+            i += len;
+        } else if b[i..].starts_with(b"::") {
+            tokens.push(Token::PathSep);
+            i += 2;
+        } else {
+            if !b[i].is_ascii_whitespace() {
+                tokens.push(Token::Punct(b[i]));
+            }
+            i += 1;
+        }
+    }
+    tokens
+}
+/* }}} */
+
+
+/* referenced_modules {{{ */
+/// Returns the first path segments after every `prefix::` in `code`.
+/// Groups such as `prefix::{a::X, b}` yield both `a` and `b`.
+///
+/// Time complexity is O(n)
+fn referenced_modules(code: &str, prefix: &str) -> BTreeSet<String> {
+    let tokens = tokenize(&mask(code));
+    let mut res = BTreeSet::new();
+    for i in 0..tokens.len() {
+        if tokens[i] != Token::Ident(prefix.to_string()) || tokens.get(i + 1) != Some(&Token::PathSep) {
+            continue;
+        }
+        match tokens.get(i + 2) {
+            Some(Token::Ident(name)) => {
+                res.insert(name.clone());
+            }
+            Some(Token::Punct(b'{')) => {
+                let mut depth = 0;
+                let mut expect_segment = true;
+                for token in &tokens[i + 2..] {
+                    match token {
+                        Token::Punct(b'{') => {
+                            depth += 1;
+                            expect_segment = depth == 1;
+                        }
+                        Token::Punct(b'}') => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        Token::Punct(b',') if depth == 1 => expect_segment = true,
+                        Token::Ident(name) if expect_segment => {
+                            res.insert(name.clone());
+                            expect_segment = false;
+                        }
+                        _ => expect_segment = false,
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    res.remove("self");
+    res
+}
+/* }}} */
+
+
+/* Library {{{ */
+/// Modules of the library directory, in the order they are declared in its root file.
+struct Library {
+    modules: Vec<(String, PathBuf)>,
+}
+
+impl Library {
+    fn load(dir: &Path) -> Result<Self, String> {
+        let root = ["mod.rs", "lib.rs"].iter()
+            .map(|name| dir.join(name))
+            .find(|path| path.is_file())
+            .ok_or_else(|| format!("{}: neither mod.rs nor lib.rs found", dir.display()))?;
+        let tokens = tokenize(&mask(&read(&root)?));
+
+        let mut modules = vec![];
+        for i in 0..tokens.len() {
+            if let [Token::Ident(kw), Token::Ident(name), Token::Punct(b';')] = &tokens[i..(i + 3).min(tokens.len())] {
+                if kw != "mod" {
+                    continue;
+                }
+                let path = [dir.join(format!("{}.rs", name)), dir.join(name).join("mod.rs")]
+                    .into_iter()
+                    .find(|path| path.is_file())
+                    .ok_or_else(|| format!("{}: file for module `{}` not found", dir.display(), name))?;
+                modules.push((name.clone(), path));
+            }
+        }
+        Ok(Self { modules })
+    }
+
+    fn path(&self, name: &str) -> Option<&Path> {
+        self.modules.iter().find(|(n, _)| n == name).map(|(_, p)| p.as_path())
+    }
+
+    /// Returns the modules needed by `roots`, following `super::` references between modules.
+    fn resolve(&self, roots: BTreeSet<String>) -> Result<Vec<(String, String)>, String> {
+        let mut used = BTreeSet::new();
+        let mut st = roots.into_iter().collect::<Vec<_>>();
+        while let Some(name) = st.pop() {
+            if used.contains(&name) {
+                continue;
+            }
+            let path = self.path(&name).ok_or_else(|| format!("unknown module `libs::{}`", name))?;
+            referenced_modules(&strip_tests(&read(path)?), "super").into_iter()
+                .filter(|dep| self.path(dep).is_some())
+                .for_each(|dep| st.push(dep));
+            used.insert(name);
+        }
+
+        let mut res = vec![];
+        for (name, path) in &self.modules {
+            if used.contains(name) {
+                res.push((name.clone(), strip_tests(&read(path)?)));
+            }
+        }
+        Ok(res)
+    }
+}
+/* }}} */
+
+
+/* bundle {{{ */
+/// Returns `solution` with the used library modules inlined.
+fn bundle(solution: &str, library: &Library) -> Result<String, String> {
+    let modules = library.resolve(referenced_modules(solution, "libs"))?;
+
+    let mut block = vec![];
+    if !modules.is_empty() {
+        block.push("/* libs {{{ */".to_string());
+        block.push("#[allow(dead_code)]".to_string());
+        block.push("mod libs {".to_string());
+        for (i, (name, content)) in modules.iter().enumerate() {
+            if i > 0 {
+                block.push(String::new());
+            }
+            block.push(format!("    /* {} {{{{{{ */", name));
+            block.push(format!("    pub mod {} {{", name));
+            block.extend(indent(content, 8));
+            block.push("    }".to_string());
+            block.push("    /* }}} */".to_string());
+        }
+        block.push("}".to_string());
+        block.push("/* }}} */".to_string());
+    }
+
+    let mut lines = solution.lines().map(|line| line.to_string()).collect::<Vec<_>>();
+    if let Some(pos) = lines.iter().position(|line| line.trim() == "mod libs;") {
+        lines.splice(pos..=pos, block);
+    } else if !block.is_empty() {
+        let pos = match lines.iter().position(|line| line.trim() == "/* imports {{{ */") {
+            Some(begin) => lines[begin..].iter().position(|line| line.trim() == "/* }}} */").map_or(0, |end| begin + end + 1),
+            None => 0,
+        };
+        block.insert(0, String::new());
+        lines.splice(pos..pos, block);
+    }
+
+    let mut res = lines.join("\n");
+    res.push('\n');
+    Ok(res)
+}
+
+/// Trims surrounding blank lines and indents every non-blank line by `width` spaces.
+fn indent(content: &str, width: usize) -> Vec<String> {
+    let lines = content.lines().collect::<Vec<_>>();
+    let begin = lines.iter().position(|line| !line.trim().is_empty()).unwrap_or(lines.len());
+    let end = lines.iter().rposition(|line| !line.trim().is_empty()).map_or(begin, |p| p + 1);
+    lines[begin..end].iter()
+        .map(|line| if line.trim().is_empty() { String::new() } else { format!("{}{}", " ".repeat(width), line) })
+        .collect()
+}
+/* }}} */
+
+
+fn read(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Returns `libs/src` of the nearest ancestor of `solution` that has one.
+fn find_libs(solution: &Path) -> Result<PathBuf, String> {
+    let solution = std::fs::canonicalize(solution).map_err(|e| format!("{}: {}", solution.display(), e))?;
+    solution.ancestors()
+        .map(|dir| dir.join("libs").join("src"))
+        .find(|dir| dir.join("lib.rs").is_file())
+        .ok_or_else(|| format!("{}: no libs/src/lib.rs found in its ancestors", solution.display()))
+}
+
+fn run() -> Result<(), String> {
+    let mut solution = None;
+    let mut libs = None;
+    let mut output = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--libs" => libs = Some(PathBuf::from(args.next().ok_or("--libs needs a directory")?)),
+            "-o" | "--output" => output = Some(PathBuf::from(args.next().ok_or("-o needs a file")?)),
+            _ if solution.is_none() => solution = Some(PathBuf::from(arg)),
+            _ => return Err(format!("unexpected argument `{}`", arg)),
+        }
+    }
+    let solution = solution.ok_or("usage: bundler <solution.rs> [--libs <dir>] [-o <output>]")?;
+    let libs = match libs {
+        Some(libs) => libs,
+        None => find_libs(&solution)?,
+    };
+
+    let bundled = bundle(&read(&solution)?, &Library::load(&libs)?)?;
+    match output {
+        Some(path) => std::fs::write(&path, bundled).map_err(|e| format!("{}: {}", path.display(), e))?,
+        None => print!("{}", bundled),
+    }
+    Ok(())
+}
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("bundler: {}", e);
+        std::process::exit(1);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn library(files: &[(&str, &str)]) -> Library {
+        let dir = std::env::temp_dir().join(format!("bundler-test-{}-{}", std::process::id(), files.len()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for (name, content) in files {
+            std::fs::write(dir.join(name), content).unwrap();
+        }
+        Library::load(&dir).unwrap()
+    }
+
+    /// Compiles and runs `src` with rustc, linking the crates built as dependencies of this test, and returns its output.
+    fn run_bundled(src: &str, crates: &[&str]) -> String {
+        let deps = std::env::current_exe().unwrap().parent().unwrap().to_path_buf();
+        let dir = std::env::temp_dir().join(format!("bundler-run-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("main.rs"), src).unwrap();
+        let mut rustc = std::process::Command::new(std::env::var("RUSTC").unwrap_or("rustc".to_string()));
+        rustc.args(["--edition", "2021", "-o"]).arg(dir.join("main")).arg("-L").arg(format!("dependency={}", deps.display()));
+        for name in crates {
+            // The most recent build if several are left in the directory.
+            let rlib = std::fs::read_dir(&deps).unwrap()
+                .map(|entry| entry.unwrap().path())
+                .filter(|path| path.file_name().unwrap().to_str().unwrap().starts_with(&format!("lib{}-", name)) && path.extension().is_some_and(|ext| ext == "rlib"))
+                .max_by_key(|path| path.metadata().unwrap().modified().unwrap())
+                .unwrap_or_else(|| panic!("no rlib for `{}` in {}", name, deps.display()));
+            rustc.arg("--extern").arg(format!("{}={}", name, rlib.display()));
+        }
+        let compiled = rustc.arg(dir.join("main.rs")).output().unwrap();
+        assert!(compiled.status.success(), "{}", String::from_utf8_lossy(&compiled.stderr));
+        let output = std::process::Command::new(dir.join("main")).output().unwrap();
+        String::from_utf8(output.stdout).unwrap()
+    }
+
+    #[test]
+    fn mask_hides_comments_and_literals() {
+        let src = "a // libs::x\nb /* libs::y /* nested */ */ \"libs::z\" r#\"libs::w\"# 'c' '\\'' &'a str";
+        let masked = mask(src);
+        assert_eq!(masked.len(), src.len());
+        assert!(!masked.contains("libs"));
+        assert!(masked.contains("&'a str"));
+        assert_eq!(masked.lines().count(), 2);
+    }
+
+    #[test]
+    fn referenced_modules_follow_groups() {
+        let code = "use libs::{graph::{Graph, Edge}, prime, self};\nuse libs::monoid::Xor;\nlet x = libs::ring::Ring::new(3);";
+        let names = referenced_modules(code, "libs").into_iter().collect::<Vec<_>>();
+        assert_eq!(names, vec!["graph", "monoid", "prime", "ring"]);
+        assert!(referenced_modules("// use libs::graph;", "libs").is_empty());
+    }
+
+    #[test]
+    fn strip_tests_removes_test_modules() {
+        let src = "fn f() {}\n\n#[cfg(test)]\nmod tests {\n    fn g() { let s = \"}\"; }\n}\nfn h() {}\n";
+        assert_eq!(strip_tests(src), "fn f() {}\n\nfn h() {}\n");
+    }
+
+    #[test]
+    fn bundle_inlines_used_modules_transitively() {
+        let library = library(&[
+            ("lib.rs", "pub mod a;\npub mod b;\npub mod c;\n"),
+            ("a.rs", "\n/* A {{{ */\npub struct A;\n/* }}} */\n"),
+            ("b.rs", "pub use super::a::A;\n\n#[cfg(test)]\nmod tests {\n    use super::c::C;\n}\n"),
+            ("c.rs", "pub struct C;\n"),
+        ]);
+        let solution = "/* imports {{{ */\nuse libs::b::A;\n/* }}} */\n\nfn main() {}\n";
+        let bundled = bundle(solution, &library).unwrap();
+        assert!(bundled.starts_with("/* imports {{{ */\nuse libs::b::A;\n/* }}} */\n\n/* libs {{{ */\n"));
+        assert!(bundled.contains("    /* a {{{ */\n    pub mod a {\n        /* A {{{ */\n        pub struct A;\n"));
+        assert!(bundled.contains("    pub mod b {\n        pub use super::a::A;\n    }\n"));
+        assert!(!bundled.contains("pub mod c"));
+        assert!(bundled.ends_with("/* }}} */\n\nfn main() {}\n"));
+
+        let bundled = bundle("mod libs;\nuse libs::c::C;\n", &library).unwrap();
+        assert!(bundled.starts_with("/* libs {{{ */\n#[allow(dead_code)]\nmod libs {\n    /* c {{{ */\n"));
+        assert!(bundle("use libs::d::D;\n", &library).is_err());
+    }
+
+    #[test]
+    fn bundled_solution_compiles() {
+        let solution = r#"/* imports {{{ */
+use libs::graph::{Adjacency, Graph};
+use libs::monoid_dsu::MonoidDsu;
+/* }}} */
+
+struct Sum;
+impl ac_library::Monoid for Sum {
+    type S = i64;
+    fn identity() -> i64 {
+        0
+    }
+    fn binary_operation(a: &i64, b: &i64) -> i64 {
+        a + b
+    }
+}
+
+fn main() {
+    let mut g = Graph::new(3);
+    g.add_edge(0, 1, 5);
+    g.add_edge(1, 2, 7);
+    println!("{}", g.dijkstra(0).len());
+    let mut dsu = MonoidDsu::<Sum>::new(vec![1, 2, 3]);
+    dsu.merge(0, 2);
+    println!("{}", dsu.get_val(2));
+}
+"#;
+        let library = Library::load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("../libs/src")).unwrap();
+        let bundled = bundle(solution, &library).unwrap();
+        assert_eq!(run_bundled(&bundled, &["ac_library", "itertools", "num", "num_traits", "rand", "superslice"]), "3\n4\n");
+    }
+}
@@ -8,6 +8,7 @@ use proconio::{
 use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, VecDeque};
 use superslice::Ext;
 use rand::Rng;
+#[allow(clippy::single_component_path_imports)]
 use ac_library;
 /* }}} */
 
@@ -7,6 +7,7 @@ use proconio::{
 };
 use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, VecDeque};
 use superslice::Ext;
+#[allow(clippy::single_component_path_imports)]
 use ac_library;
 /* }}} */
 
@@ -1,13 +0,0 @@
-mod weighted_dsu;
-mod prime;
-mod rolling_hash;
-mod graph;
-mod ahc;
-mod linear_programming;
-mod zobrist_set;
-mod zobrist_multiset;
-mod coord;
-mod break_point;
-mod ring;
-mod monoid_dsu;
-mod timer;
@@ -1,5 +1,3 @@
-mod libs;
-
 fn main() {
     println!("Hello, world!");
 }
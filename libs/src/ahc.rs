@@ -0,0 +1,169 @@
+
+/* StateTrait {{{ */
+/// Trait for state representation.
+///
+/// Implement `legal_actions`, `forward`, `score` and `is_done` for the problem's state,
+/// then pick the next action with `random_action`, `greedy_action` or `beam_search`.
+pub trait StateTrait: Clone {
+
+    type Action: Clone;
+    type Score: Ord;
+
+
+    fn legal_actions(&self) -> Vec<Self::Action>;
+    fn forward(&mut self, action: &Self::Action);
+    fn score(&self) -> Self::Score;
+    fn is_done(&self) -> bool;
+    fn dbg(&self) {}
+
+
+    /* random_action {{{ */
+    /// Returns one of the legal actions uniformly at random.
+    fn random_action<R: rand::Rng>(&self, rng: &mut R) -> Option<Self::Action> {
+        let legal_actions = self.legal_actions();
+        if legal_actions.is_empty() {
+            return None;
+        }
+        return Some(legal_actions[rng.gen_range(0..legal_actions.len())].clone());
+    }
+    /* }}} */
+
+
+    /* greedy_action {{{ */
+    /// Returns the legal action whose next state has the highest score.
+    /// Returns `None` if no action improves the current score.
+    fn greedy_action(&self) -> Option<Self::Action> {
+        let mut best_score = self.score();
+        let mut best_action = None;
+        for action in self.legal_actions() {
+            let mut next_state = self.clone();
+            next_state.forward(&action);
+            let next_score = next_state.score();
+            if next_score > best_score {
+                best_score = next_score;
+                best_action = Some(action);
+            }
+        }
+        return best_action;
+    }
+    /* }}} */
+
+
+    /* beam_search {{{ */
+    /// Returns the first action of the best state found by a beam search
+    /// keeping `beam_width` states for up to `beam_depth` steps.
+    /// Returns `None` if there is no legal action.
+    ///
+    /// Time complexity is O(beam_width * beam_depth * A) forward calls, where A is the number of legal actions
+    fn beam_search(&self, beam_width: usize, beam_depth: usize) -> Option<Self::Action> {
+        let mut beam: Vec<(Self, Option<Self::Action>)> = Vec::new();
+        let mut next_beam = Vec::new();
+
+        beam.push((self.clone(), None));
+
+        for t in 0..beam_depth {
+            next_beam.clear();
+
+            while let Some((state, first_action)) = beam.pop() {
+                for action in state.legal_actions() {
+                    let mut next_state = state.clone();
+                    next_state.forward(&action);
+                    if t == 0 {
+                        next_beam.push((next_state, Some(action)));
+                    } else {
+                        next_beam.push((next_state, first_action.clone()));
+                    }
+                }
+            }
+
+            next_beam.sort_by_key(|(state, _)| std::cmp::Reverse(state.score()));
+            next_beam.truncate(beam_width);
+
+            std::mem::swap(&mut beam, &mut next_beam);
+
+            match beam.first() {
+                Some((state, _)) if state.is_done() => break,
+                None => break,
+                _ => (),
+            }
+        }
+
+        return beam.into_iter().max_by(|a, b| a.0.score().cmp(&b.0.score())).and_then(|(_, action)| action);
+    }
+    /* }}} */
+
+}
+/* }}} */
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    /// Walks from `pos` towards `target` with steps of +1, +2 or *2.
+    #[derive(Clone)]
+    struct Walk {
+        pos: i64,
+        target: i64,
+    }
+
+    impl StateTrait for Walk {
+        type Action = i64;
+        type Score = i64;
+
+        fn legal_actions(&self) -> Vec<i64> {
+            if self.is_done() {
+                return vec![];
+            }
+            vec![self.pos + 1, self.pos + 2, self.pos * 2]
+        }
+
+        fn forward(&mut self, action: &i64) {
+            self.pos = *action;
+        }
+
+        fn score(&self) -> i64 {
+            -(self.target - self.pos).abs()
+        }
+
+        fn is_done(&self) -> bool {
+            self.pos == self.target
+        }
+    }
+
+    #[test]
+    fn random_action_is_legal() {
+        let state = Walk { pos: 3, target: 10 };
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+        for _ in 0..20 {
+            let action = state.random_action(&mut rng).unwrap();
+            assert!(state.legal_actions().contains(&action));
+        }
+        assert_eq!(Walk { pos: 10, target: 10 }.random_action(&mut rng), None);
+    }
+
+    #[test]
+    fn greedy_action_improves_score() {
+        assert_eq!(Walk { pos: 3, target: 10 }.greedy_action(), Some(6));
+        assert_eq!(Walk { pos: 9, target: 10 }.greedy_action(), Some(10));
+        assert_eq!(Walk { pos: 10, target: 10 }.greedy_action(), None);
+    }
+
+    #[test]
+    fn beam_search_looks_ahead() {
+        // Greedy walks 3 -> 6 -> 12 -> 14 -> 16, while 3 -> 4 -> 8 -> 16 is one step shorter.
+        let steps = |select: &dyn Fn(&Walk) -> Option<i64>| {
+            let mut state = Walk { pos: 3, target: 16 };
+            let mut steps = 0;
+            while let Some(action) = select(&state) {
+                state.forward(&action);
+                steps += 1;
+            }
+            assert!(state.is_done());
+            steps
+        };
+        assert_eq!(steps(&|state| state.greedy_action()), 4);
+        assert_eq!(steps(&|state| state.beam_search(10, 3)), 3);
+    }
+}
@@ -1,8 +1,12 @@
-fn break_point() {
-    use rfd;
+
+/* break_point {{{ */
+/// Blocks until the "next" button of a dialog is pressed.
+/// Requires the `break_point` feature.
+pub fn break_point() {
     let _result = rfd::MessageDialog::new()
         .set_title("break point")
         .set_description("next")
         .set_buttons(rfd::MessageButtons::Ok)
         .show();
 }
+/* }}} */
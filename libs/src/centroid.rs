@@ -0,0 +1,276 @@
+
+/* CentroidDecomposition {{{ */
+/// Centroid decomposition of a tree.
+///
+/// Every path of the tree passes through the shallowest centroid among its vertices,
+/// and every vertex has O(log V) centroid ancestors.
+#[derive(Clone, Debug)]
+pub struct CentroidDecomposition {
+    pub n: usize,
+    /// Parent of each vertex in the centroid tree, or `None` for the first centroid.
+    pub parent: Vec<Option<usize>>,
+    /// Depth of each vertex in the centroid tree.
+    pub level: Vec<usize>,
+    /// Vertices in the order they were chosen as centroids, so every parent comes before its children.
+    pub order: Vec<usize>,
+    adj: Vec<Vec<usize>>,
+    /// `dist[v][l]` is the distance from `v` to its centroid ancestor at level `l`.
+    dist: Vec<Vec<usize>>,
+}
+
+impl CentroidDecomposition {
+    /* new {{{ */
+    /// Decomposes `tree`.
+    ///
+    /// Time complexity is O(V log V)
+    pub fn new(tree: &super::tree::Tree) -> Self {
+        let n = tree.n;
+        let mut adj = tree.children.clone();
+        for v in 0..n {
+            if let Some(p) = tree.parent[v] {
+                adj[v].push(p);
+            }
+        }
+        let mut cd = Self {n, parent: vec![None; n], level: vec![usize::MAX; n], order: vec![], adj, dist: vec![vec![]; n]};
+        if n == 0 {
+            return cd;
+        }
+
+        let mut stack = vec![(tree.root, None)];
+        while let Some((start, parent)) = stack.pop() {
+            let (vertices, _, prev) = cd.component(start);
+            let total = vertices.len();
+            let mut size = vec![1; total];
+            let mut max_child = vec![0; total];
+            for i in (1..total).rev() {
+                size[prev[i]] += size[i];
+                max_child[prev[i]] = max_child[prev[i]].max(size[i]);
+            }
+            // No side left after removing the centroid has more than half of the vertices.
+            let c = (0..total).find(|&i| 2 * max_child[i] <= total && 2 * (total - size[i]) <= total).map(|i| vertices[i]).unwrap();
+
+            cd.level[c] = parent.map_or(0, |p: usize| cd.level[p] + 1);
+            cd.parent[c] = parent;
+            cd.order.push(c);
+            let (vertices, dist, _) = cd.component(c);
+            for (&v, &d) in vertices.iter().zip(&dist) {
+                cd.dist[v].push(d);
+            }
+            for &to in &cd.adj[c] {
+                if cd.level[to] == usize::MAX {
+                    stack.push((to, Some(c)));
+                }
+            }
+        }
+        return cd;
+    }
+    /* }}} */
+
+
+    /* component {{{ */
+    /// Returns the vertices reachable from `start` through vertices of the same or a deeper level, in BFS order,
+    /// with their distances from `start` and the index of their BFS parent in the result.
+    /// Vertices not yet chosen as centroids have the deepest level.
+    fn component(&self, start: usize) -> (Vec<usize>, Vec<usize>, Vec<usize>) {
+        let mut vertices = vec![start];
+        let mut dist = vec![0];
+        let mut prev = vec![usize::MAX];
+        let mut i = 0;
+        while i < vertices.len() {
+            let v = vertices[i];
+            for &to in &self.adj[v] {
+                if self.level[to] >= self.level[start] && (i == 0 || to != vertices[prev[i]]) {
+                    vertices.push(to);
+                    dist.push(dist[i] + 1);
+                    prev.push(i);
+                }
+            }
+            i += 1;
+        }
+        return (vertices, dist, prev);
+    }
+    /* }}} */
+
+
+    /* components {{{ */
+    /// Returns an iterator over `(centroid, vertices, distances)` for each centroid in `order`,
+    /// where `vertices` is the component the centroid splits, starting with itself, and `distances` are from the centroid.
+    ///
+    /// Time complexity is O(V log V) in total
+    pub fn components(&self) -> impl Iterator<Item = (usize, Vec<usize>, Vec<usize>)> + '_ {
+        return self.order.iter().map(move |&c| {
+            let (vertices, dist, _) = self.component(c);
+            (c, vertices, dist)
+        });
+    }
+    /* }}} */
+
+
+    /* ancestors {{{ */
+    /// Returns the centroid ancestors of `v` from `v` itself up to the first centroid, with their distances to `v`.
+    ///
+    /// Time complexity is O(log V)
+    pub fn ancestors(&self, v: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        return std::iter::successors(Some(v), move |&c| self.parent[c]).map(move |c| (c, self.dist[v][self.level[c]]));
+    }
+    /* }}} */
+
+
+    /* count_pairs_within {{{ */
+    /// Returns the number of unordered pairs of distinct vertices at distance at most `k`.
+    ///
+    /// Time complexity is O(V log^2 V)
+    pub fn count_pairs_within(&self, k: usize) -> u64 {
+        // Pairs i < j with a[i] + a[j] <= k.
+        let count = |a: &mut Vec<usize>| -> u64 {
+            a.sort_unstable();
+            let mut res = 0;
+            let mut r = a.len();
+            for l in 0..a.len() {
+                while r > l + 1 && a[l] + a[r - 1] > k {
+                    r -= 1;
+                }
+                if r <= l + 1 {
+                    break;
+                }
+                res += (r - l - 1) as u64;
+            }
+            return res;
+        };
+        let mut result = 0;
+        for &c in &self.order {
+            let (_, mut dist, prev) = self.component(c);
+            // Pairs within the same branch below `c` do not pass through it.
+            let mut branch = vec![0; dist.len()];
+            let mut branches = vec![vec![]; dist.len()];
+            for i in 1..dist.len() {
+                branch[i] = if prev[i] == 0 { i } else { branch[prev[i]] };
+                branches[branch[i]].push(dist[i]);
+            }
+            result += count(&mut dist);
+            for a in &mut branches {
+                result -= count(a);
+            }
+        }
+        return result;
+    }
+    /* }}} */
+}
+/* }}} */
+
+
+/* NearestMarked {{{ */
+/// Distance from a vertex to the nearest marked vertex, with marks added and removed online.
+#[derive(Clone, Debug)]
+pub struct NearestMarked {
+    pub cd: CentroidDecomposition,
+    /// Marked vertices in the component of each centroid, keyed by distance to it.
+    marked: Vec<std::collections::BTreeSet<(usize, usize)>>,
+}
+
+impl NearestMarked {
+    pub fn new(cd: CentroidDecomposition) -> Self {
+        return Self {marked: vec![std::collections::BTreeSet::new(); cd.n], cd};
+    }
+
+    /// Time complexity is O(log^2 V)
+    pub fn mark(&mut self, v: usize) {
+        for (c, d) in self.cd.ancestors(v) {
+            self.marked[c].insert((d, v));
+        }
+    }
+
+    /// Time complexity is O(log^2 V)
+    pub fn unmark(&mut self, v: usize) {
+        for (c, d) in self.cd.ancestors(v) {
+            self.marked[c].remove(&(d, v));
+        }
+    }
+
+    /// Returns the distance from `v` to the nearest marked vertex, or `None` if none is marked.
+    ///
+    /// Time complexity is O(log^2 V)
+    pub fn nearest(&self, v: usize) -> Option<usize> {
+        return self.cd.ancestors(v).filter_map(|(c, d)| self.marked[c].first().map(|&(e, _)| d + e)).min();
+    }
+}
+/* }}} */
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tree::Tree;
+    use rand::{Rng, SeedableRng};
+
+    fn random_tree(rng: &mut rand::rngs::SmallRng, n: usize) -> Tree {
+        let parent = (0..n).map(|v| if v == 0 { None } else { Some(rng.gen_range(0..v)) }).collect::<Vec<_>>();
+        return Tree::from_parents(&parent);
+    }
+
+    #[test]
+    fn structure() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+        for _ in 0..30 {
+            let n = rng.gen_range(1..50);
+            let t = random_tree(&mut rng, n);
+            let cd = CentroidDecomposition::new(&t);
+            assert_eq!(cd.order.len(), n);
+            assert_eq!(cd.parent.iter().filter(|p| p.is_none()).count(), 1);
+            let mut total = 0;
+            for (c, vertices, dist) in cd.components() {
+                assert_eq!(vertices[0], c);
+                // Each part left after removing the centroid has at most half of the vertices.
+                for &v in &vertices[1..] {
+                    let under = vertices.iter().filter(|&&u| cd.ancestors(u).any(|(a, _)| a == v)).count();
+                    assert!(2 * under <= vertices.len());
+                }
+                for (&v, &d) in vertices.iter().zip(&dist) {
+                    assert_eq!(d, t.dist(c, v));
+                    assert!(cd.ancestors(v).any(|(a, _)| a == c));
+                }
+                total += vertices.len();
+            }
+            assert!(n <= 1 || total <= n * (1 + (usize::BITS - n.leading_zeros()) as usize));
+            for v in 0..n {
+                assert_eq!(cd.ancestors(v).count(), cd.level[v] + 1);
+            }
+        }
+    }
+
+    #[test]
+    fn count_pairs_within() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(1);
+        for _ in 0..30 {
+            let n = rng.gen_range(1..40);
+            let t = random_tree(&mut rng, n);
+            let cd = CentroidDecomposition::new(&t);
+            for k in 0..6 {
+                let naive = (0..n).flat_map(|u| (u + 1..n).map(move |v| (u, v))).filter(|&(u, v)| t.dist(u, v) <= k).count();
+                assert_eq!(cd.count_pairs_within(k), naive as u64);
+            }
+        }
+    }
+
+    #[test]
+    fn nearest_marked() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(2);
+        for _ in 0..20 {
+            let n = rng.gen_range(1..40);
+            let t = random_tree(&mut rng, n);
+            let mut nm = NearestMarked::new(CentroidDecomposition::new(&t));
+            let mut marked = vec![false; n];
+            for _ in 0..50 {
+                let v = rng.gen_range(0..n);
+                if marked[v] {
+                    nm.unmark(v);
+                } else {
+                    nm.mark(v);
+                }
+                marked[v] = !marked[v];
+                let u = rng.gen_range(0..n);
+                assert_eq!(nm.nearest(u), (0..n).filter(|&w| marked[w]).map(|w| t.dist(u, w)).min());
+            }
+        }
+    }
+}
@@ -0,0 +1,37 @@
+
+/* Coord {{{ */
+/// Point on the plane.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Coord<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T: num_traits::real::Real + Clone + Copy> Coord<T> {
+    pub fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    /// Returns the Euclidean distance to `other`.
+    pub fn dist(&self, other: Self) -> T {
+        let dx = self.x - other.x;
+        let dy = self.y - other.y;
+        (dx * dx + dy * dy).sqrt()
+    }
+}
+/* }}} */
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dist() {
+        let a = Coord::new(1.0, 2.0);
+        let b = Coord::new(4.0, 6.0);
+        assert_eq!(a.dist(b), 5.0);
+        assert_eq!(b.dist(a), 5.0);
+        assert_eq!(a.dist(a), 0.0);
+    }
+}
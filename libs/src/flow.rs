@@ -0,0 +1,522 @@
+
+/* MaxFlow {{{ */
+/// Maximum flow on `ac_library::MfGraph` over a `Graph` whose weights are capacities.
+///
+/// An undirected edge can carry flow either way, up to its capacity in each direction.
+pub struct MaxFlow {
+    pub n: usize,
+    pub mf: ac_library::MfGraph<i64>,
+    /// Edge ID in the `Graph` of each edge of `mf`.
+    ids: Vec<usize>,
+    /// `from` of each edge of the `Graph`, which orients `edge_flows`.
+    from: Vec<usize>,
+}
+
+/// Minimum s-t cut.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct MinCut {
+    pub value: i64,
+    /// Whether each vertex is on the source side, which is the set of vertices reachable from the source in the residual graph.
+    pub source_side: Vec<bool>,
+    /// IDs of the edges from the source side to the sink side, in increasing order.
+    pub edges: Vec<usize>,
+}
+
+impl MaxFlow {
+    /* new {{{ */
+    /// Builds the network of `g`, with one edge of `mf` for each edge leaving each vertex.
+    ///
+    /// Time complexity is O(V + E)
+    pub fn new(g: &impl super::graph::Adjacency<W = i64>) -> Self {
+        let n = g.num_vertices();
+        let mut mf = ac_library::MfGraph::new(n);
+        let mut ids = vec![];
+        for v in 0..n {
+            for &(id, to, cap) in g.out_edges(v) {
+                mf.add_edge(v, to, cap);
+                ids.push(id);
+            }
+        }
+        let from = g.edge_list().iter().map(|e| e.from).collect();
+        return Self {n, mf, ids, from};
+    }
+    /* }}} */
+
+
+    /// Pushes as much flow as possible from `s` to `t` and returns the amount added.
+    ///
+    /// Time complexity is O(V^2 E)
+    pub fn flow(&mut self, s: usize, t: usize) -> i64 {
+        return self.mf.flow(s, t);
+    }
+
+
+    /* min_cut {{{ */
+    /// Returns a minimum cut between `s` and `t` after pushing the maximum flow, so that the source side is the smallest.
+    ///
+    /// Time complexity is O(V^2 E)
+    pub fn min_cut(&mut self, s: usize, t: usize) -> MinCut {
+        self.mf.flow(s, t);
+        let source_side = self.mf.min_cut(s);
+        let mut value = 0;
+        let mut edges = vec![];
+        for (i, e) in self.mf.edges().into_iter().enumerate() {
+            if source_side[e.from] && !source_side[e.to] && e.cap > 0 {
+                value += e.cap;
+                edges.push(self.ids[i]);
+            }
+        }
+        edges.sort_unstable();
+        return MinCut {value, source_side, edges};
+    }
+    /* }}} */
+
+
+    /// Returns the flow along each edge of the `Graph`, from `from` to `to`, which is negative if an undirected edge carries it backwards.
+    ///
+    /// Time complexity is O(E)
+    pub fn edge_flows(&self) -> Vec<i64> {
+        let mut res = vec![0; self.from.len()];
+        for (i, e) in self.mf.edges().into_iter().enumerate() {
+            let id = self.ids[i];
+            res[id] += if e.from == self.from[id] { e.flow } else { -e.flow };
+        }
+        return res;
+    }
+}
+/* }}} */
+
+
+/* LowerBoundFlow {{{ */
+/// Flow where every edge carries between a lower and an upper bound,
+/// reduced to a maximum flow from a super source to a super sink that must saturate the lower bounds.
+#[derive(Clone, Debug)]
+pub struct LowerBoundFlow {
+    pub n: usize,
+    /// `(from, to, lower, upper)` for each edge.
+    pub edges: Vec<(usize, usize, i64, i64)>,
+}
+
+impl LowerBoundFlow {
+    pub fn new(n: usize) -> Self {
+        return Self {n, edges: vec![]};
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize, lower: i64, upper: i64) -> usize {
+        assert!(0 <= lower && lower <= upper);
+        self.edges.push((from, to, lower, upper));
+        return self.edges.len() - 1;
+    }
+
+    /// Builds the reduced network, with edges between `s` and `t` in both directions if given,
+    /// and returns it with whether the lower bounds can be met.
+    fn feasible(&self, st: Option<(usize, usize)>) -> (ac_library::MfGraph<i64>, bool) {
+        let (src, dst) = (self.n, self.n + 1);
+        let mut mf = ac_library::MfGraph::new(self.n + 2);
+        let mut excess = vec![0; self.n];
+        for &(from, to, lower, upper) in &self.edges {
+            mf.add_edge(from, to, upper - lower);
+            excess[to] += lower;
+            excess[from] -= lower;
+        }
+        if let Some((s, t)) = st {
+            let inf = self.edges.iter().map(|e| e.3).sum();
+            mf.add_edge(t, s, inf);
+            mf.add_edge(s, t, inf);
+        }
+        let mut need = 0;
+        for v in 0..self.n {
+            if excess[v] > 0 {
+                mf.add_edge(src, v, excess[v]);
+                need += excess[v];
+            } else if excess[v] < 0 {
+                mf.add_edge(v, dst, -excess[v]);
+            }
+        }
+        let ok = mf.flow(src, dst) == need;
+        return (mf, ok);
+    }
+
+    /// Returns the reduced network holding a feasible flow from `s` to `t` with its value, or `None` if there is none.
+    fn feasible_st(&self, s: usize, t: usize) -> Option<(ac_library::MfGraph<i64>, i64)> {
+        let (mut mf, ok) = self.feasible(Some((s, t)));
+        if !ok {
+            return None;
+        }
+        // The flow returning from `t` to `s` is the value of a feasible s-t flow.
+        // Removing those edges leaves the residual graph of that flow.
+        let (back, forward) = (self.edges.len(), self.edges.len() + 1);
+        let value = mf.get_edge(back).flow - mf.get_edge(forward).flow;
+        mf.change_edge(back, 0, 0);
+        mf.change_edge(forward, 0, 0);
+        return Some((mf, value));
+    }
+
+    fn flows(&self, mf: &ac_library::MfGraph<i64>) -> Vec<i64> {
+        return self.edges.iter().enumerate().map(|(i, e)| e.2 + mf.get_edge(i).flow).collect();
+    }
+
+
+    /* circulation {{{ */
+    /// Returns the flow along each edge of a circulation meeting the bounds, or `None` if there is none.
+    ///
+    /// Time complexity is O(V^2 E)
+    pub fn circulation(&self) -> Option<Vec<i64>> {
+        let (mf, ok) = self.feasible(None);
+        return if ok { Some(self.flows(&mf)) } else { None };
+    }
+    /* }}} */
+
+
+    /* max_flow {{{ */
+    /// Returns the maximum flow from `s` to `t` meeting the bounds with the flow along each edge,
+    /// or `None` if the bounds cannot be met.
+    /// The value is the net flow out of `s`, which is negative if the bounds force flow from `t` to `s`.
+    ///
+    /// Time complexity is O(V^2 E)
+    pub fn max_flow(&self, s: usize, t: usize) -> Option<(i64, Vec<i64>)> {
+        let (mut mf, value) = self.feasible_st(s, t)?;
+        let more = mf.flow(s, t);
+        return Some((value + more, self.flows(&mf)));
+    }
+    /* }}} */
+
+
+    /* min_flow {{{ */
+    /// Same as `max_flow` for the minimum flow.
+    ///
+    /// Time complexity is O(V^2 E)
+    pub fn min_flow(&self, s: usize, t: usize) -> Option<(i64, Vec<i64>)> {
+        let (mut mf, value) = self.feasible_st(s, t)?;
+        let less = mf.flow(t, s);
+        return Some((value - less, self.flows(&mf)));
+    }
+    /* }}} */
+}
+/* }}} */
+
+
+/* BipartiteMatching {{{ */
+/// Maximum matching between `left` and `right` vertices with Hopcroft–Karp,
+/// and the minimum vertex cover and maximum independent set derived from it by König's theorem.
+#[derive(Clone, Debug)]
+pub struct BipartiteMatching {
+    pub left: usize,
+    pub right: usize,
+    adj: Vec<Vec<usize>>,
+    /// Right vertex matched to each left vertex.
+    pub match_left: Vec<Option<usize>>,
+    /// Left vertex matched to each right vertex.
+    pub match_right: Vec<Option<usize>>,
+}
+
+impl BipartiteMatching {
+    pub fn new(left: usize, right: usize) -> Self {
+        return Self {left, right, adj: vec![vec![]; left], match_left: vec![None; left], match_right: vec![None; right]};
+    }
+
+    pub fn add_edge(&mut self, l: usize, r: usize) {
+        assert!(r < self.right);
+        self.adj[l].push(r);
+    }
+
+
+    /* from_graph {{{ */
+    /// Builds the matching problem of `g`, whose vertices below `left` form the left side and the others the right side.
+    /// Edges may point either way but must join the two sides, and their weights are ignored.
+    ///
+    /// Time complexity is O(V + E)
+    pub fn from_graph(left: usize, g: &impl super::graph::Adjacency) -> Self {
+        assert!(left <= g.num_vertices());
+        let mut res = Self::new(left, g.num_vertices() - left);
+        for e in g.edge_list() {
+            let (l, r) = if e.from < e.to { (e.from, e.to) } else { (e.to, e.from) };
+            assert!(l < left && left <= r, "edge {} does not join the two sides", e.id);
+            res.add_edge(l, r - left);
+        }
+        return res;
+    }
+    /* }}} */
+
+
+    /* solve {{{ */
+    /// Extends the matching to a maximum one and returns its size.
+    /// Each phase finds a maximal set of shortest augmenting paths, and there are O(√V) phases.
+    ///
+    /// Time complexity is O(E √V)
+    pub fn solve(&mut self) -> usize {
+        loop {
+            // Layers of left vertices by the length of the shortest alternating path from a free left vertex.
+            let mut dist = vec![usize::MAX; self.left];
+            let mut que = std::collections::VecDeque::new();
+            for l in 0..self.left {
+                if self.match_left[l].is_none() {
+                    dist[l] = 0;
+                    que.push_back(l);
+                }
+            }
+            let mut found = false;
+            while let Some(l) = que.pop_front() {
+                for &r in &self.adj[l] {
+                    match self.match_right[r] {
+                        None => found = true,
+                        Some(next) => {
+                            if dist[next] == usize::MAX {
+                                dist[next] = dist[l] + 1;
+                                que.push_back(next);
+                            }
+                        }
+                    }
+                }
+            }
+            if !found {
+                break;
+            }
+
+            let mut iter = vec![0; self.left];
+            for l in 0..self.left {
+                if self.match_left[l].is_some() {
+                    continue;
+                }
+                // Iterative DFS along the layers, where `adj[v][iter[v]]` is the edge taken from each `v` on the stack.
+                let mut stack = vec![l];
+                while let Some(&v) = stack.last() {
+                    if iter[v] == self.adj[v].len() {
+                        dist[v] = usize::MAX;
+                        stack.pop();
+                        continue;
+                    }
+                    let r = self.adj[v][iter[v]];
+                    match self.match_right[r] {
+                        None => {
+                            for &u in &stack {
+                                let r = self.adj[u][iter[u]];
+                                self.match_left[u] = Some(r);
+                                self.match_right[r] = Some(u);
+                            }
+                            break;
+                        }
+                        Some(next) if dist[next] == dist[v] + 1 => stack.push(next),
+                        Some(_) => iter[v] += 1,
+                    }
+                }
+            }
+        }
+        return self.match_left.iter().flatten().count();
+    }
+    /* }}} */
+
+
+    /// Returns the matched `(left, right)` pairs in increasing order of `left`.
+    pub fn pairs(&self) -> Vec<(usize, usize)> {
+        return (0..self.left).filter_map(|l| self.match_left[l].map(|r| (l, r))).collect();
+    }
+
+
+    /* konig {{{ */
+    /// Returns whether each left and each right vertex is reachable from a free left vertex by an alternating path.
+    /// Requires a maximum matching.
+    fn konig(&self) -> (Vec<bool>, Vec<bool>) {
+        let mut seen_left = vec![false; self.left];
+        let mut seen_right = vec![false; self.right];
+        let mut stack = (0..self.left).filter(|&l| self.match_left[l].is_none()).collect::<Vec<_>>();
+        for &l in &stack {
+            seen_left[l] = true;
+        }
+        while let Some(l) = stack.pop() {
+            for &r in &self.adj[l] {
+                if !seen_right[r] {
+                    seen_right[r] = true;
+                    // `r` is matched, since otherwise the matching would not be maximum.
+                    let next = self.match_right[r].unwrap();
+                    if !seen_left[next] {
+                        seen_left[next] = true;
+                        stack.push(next);
+                    }
+                }
+            }
+        }
+        return (seen_left, seen_right);
+    }
+    /* }}} */
+
+
+    /// Returns the left and the right vertices of a minimum vertex cover, whose size equals the maximum matching.
+    /// Call `solve` first.
+    ///
+    /// Time complexity is O(V + E)
+    pub fn min_vertex_cover(&self) -> (Vec<usize>, Vec<usize>) {
+        let (seen_left, seen_right) = self.konig();
+        return ((0..self.left).filter(|&l| !seen_left[l]).collect(), (0..self.right).filter(|&r| seen_right[r]).collect());
+    }
+
+    /// Returns the left and the right vertices of a maximum independent set, the complement of `min_vertex_cover`.
+    /// Call `solve` first.
+    ///
+    /// Time complexity is O(V + E)
+    pub fn max_independent_set(&self) -> (Vec<usize>, Vec<usize>) {
+        let (seen_left, seen_right) = self.konig();
+        return ((0..self.left).filter(|&l| seen_left[l]).collect(), (0..self.right).filter(|&r| !seen_right[r]).collect());
+    }
+}
+/* }}} */
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Graph;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn max_flow() {
+        let mut g = Graph::new(4);
+        g.add_edge(0, 1, 3);
+        g.add_edge(0, 2, 2);
+        g.add_undirected_edge(1, 2, 5);
+        g.add_edge(1, 3, 2);
+        g.add_edge(2, 3, 3);
+        let mut mf = MaxFlow::new(&g);
+        let cut = mf.min_cut(0, 3);
+        assert_eq!(cut.value, 5);
+        assert_eq!(cut.source_side, vec![true, false, false, false]);
+        assert_eq!(cut.edges, vec![0, 1]);
+        let flows = mf.edge_flows();
+        assert_eq!(flows, vec![3, 2, 1, 2, 3]);
+
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let n = rng.gen_range(2..7);
+            let mut g = Graph::new(n);
+            for _ in 0..rng.gen_range(0..12) {
+                let (u, v, c) = (rng.gen_range(0..n), rng.gen_range(0..n), rng.gen_range(0..5));
+                if rng.gen() { g.add_edge(u, v, c) } else { g.add_undirected_edge(u, v, c) };
+            }
+            let cut = MaxFlow::new(&g).min_cut(0, n - 1);
+            // The cheapest cut over every partition with 0 on the source side and n - 1 on the other.
+            let naive = (0..1_usize << n).filter(|&m| m & 1 == 1 && m >> (n - 1) & 1 == 0).map(|m| {
+                let side = |v: usize| m >> v & 1 == 1;
+                (0..n).flat_map(|v| g.g[v].iter().map(move |&(_, to, c)| (v, to, c))).filter(|&(v, to, _)| side(v) && !side(to)).map(|e| e.2).sum::<i64>()
+            }).min().unwrap();
+            assert_eq!(cut.value, naive);
+            assert!(cut.source_side[0] && !cut.source_side[n - 1]);
+        }
+    }
+
+    #[test]
+    fn lower_bounds() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(1);
+        for _ in 0..200 {
+            let n = rng.gen_range(2..5);
+            let mut lb = LowerBoundFlow::new(n);
+            for _ in 0..rng.gen_range(0..6) {
+                let lower = rng.gen_range(0..3);
+                lb.add_edge(rng.gen_range(0..n), rng.gen_range(0..n), lower, lower + rng.gen_range(0..3));
+            }
+            let (s, t) = (0, n - 1);
+            // Net outflow of `s` for every assignment meeting the bounds and conserving flow at the other vertices.
+            let mut values = vec![];
+            let mut circulations = 0;
+            let total = lb.edges.iter().map(|e| e.3 - e.2 + 1).product::<i64>();
+            for mut k in 0..total {
+                let mut balance = vec![0; n];
+                for &(from, to, lower, upper) in &lb.edges {
+                    let f = lower + k % (upper - lower + 1);
+                    k /= upper - lower + 1;
+                    balance[from] -= f;
+                    balance[to] += f;
+                }
+                if (0..n).all(|v| v == s || v == t || balance[v] == 0) {
+                    values.push(-balance[s]);
+                }
+                circulations += balance.iter().all(|&b| b == 0) as usize;
+            }
+            let check = |flows: &Vec<i64>| {
+                let mut balance = vec![0; n];
+                for (i, &(from, to, lower, upper)) in lb.edges.iter().enumerate() {
+                    assert!(lower <= flows[i] && flows[i] <= upper);
+                    balance[from] -= flows[i];
+                    balance[to] += flows[i];
+                }
+                balance
+            };
+            match lb.max_flow(s, t) {
+                Some((value, flows)) => {
+                    assert_eq!(Some(value), values.iter().copied().max());
+                    assert_eq!(-check(&flows)[s], value);
+                    let (min, flows) = lb.min_flow(s, t).unwrap();
+                    assert_eq!(Some(min), values.iter().copied().min());
+                    assert_eq!(-check(&flows)[s], min);
+                }
+                None => assert!(values.is_empty()),
+            }
+            match lb.circulation() {
+                Some(flows) => assert!(check(&flows).iter().all(|&b| b == 0)),
+                None => assert_eq!(circulations, 0),
+            }
+        }
+    }
+
+    #[test]
+    fn bipartite_matching() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(2);
+        for _ in 0..200 {
+            let (left, right) = (rng.gen_range(0..6), rng.gen_range(0..6));
+            let mut bm = BipartiteMatching::new(left, right);
+            let mut edges = vec![];
+            for _ in 0..rng.gen_range(0..15) {
+                if left > 0 && right > 0 {
+                    let (l, r) = (rng.gen_range(0..left), rng.gen_range(0..right));
+                    bm.add_edge(l, r);
+                    edges.push((l, r));
+                }
+            }
+            let size = bm.solve();
+            let pairs = bm.pairs();
+            assert_eq!(pairs.len(), size);
+            assert!(pairs.iter().all(|p| edges.contains(p)));
+            // Largest set of edges with distinct endpoints.
+            let naive = (0..1_usize << edges.len()).filter(|&m| {
+                let chosen = (0..edges.len()).filter(|&i| m >> i & 1 == 1).map(|i| edges[i]).collect::<Vec<_>>();
+                let mut ls = chosen.iter().map(|e| e.0).collect::<Vec<_>>();
+                let mut rs = chosen.iter().map(|e| e.1).collect::<Vec<_>>();
+                ls.sort();
+                ls.dedup();
+                rs.sort();
+                rs.dedup();
+                ls.len() == chosen.len() && rs.len() == chosen.len()
+            }).map(|m| m.count_ones() as usize).max().unwrap();
+            assert_eq!(size, naive);
+
+            let (cl, cr) = bm.min_vertex_cover();
+            assert_eq!(cl.len() + cr.len(), size);
+            assert!(edges.iter().all(|(l, r)| cl.contains(l) || cr.contains(r)));
+            let (il, ir) = bm.max_independent_set();
+            assert_eq!(il.len() + ir.len(), left + right - size);
+            assert!(edges.iter().all(|(l, r)| !(il.contains(l) && ir.contains(r))));
+        }
+    }
+
+    #[test]
+    fn bipartite_from_graph() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(3);
+        for _ in 0..100 {
+            let (left, right) = (rng.gen_range(1..8), rng.gen_range(1..8));
+            let mut g = Graph::new(left + right);
+            let mut bm = BipartiteMatching::new(left, right);
+            for _ in 0..rng.gen_range(0..20) {
+                let (l, r) = (rng.gen_range(0..left), rng.gen_range(0..right));
+                match rng.gen_range(0..3) {
+                    0 => g.add_edge(l, left + r, 1),
+                    1 => g.add_edge(left + r, l, 1),
+                    _ => g.add_undirected_edge(l, left + r, 1),
+                };
+                bm.add_edge(l, r);
+            }
+            let mut from_graph = BipartiteMatching::from_graph(left, &g);
+            let size = from_graph.solve();
+            assert_eq!(size, bm.solve());
+            assert!(from_graph.pairs().iter().all(|&(l, r)| g.edges.iter().any(|e| (e.from, e.to) == (l, left + r) || (e.from, e.to) == (left + r, l))));
+        }
+    }
+}
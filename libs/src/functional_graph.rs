@@ -0,0 +1,209 @@
+
+/* FunctionalGraph {{{ */
+/// Graph where every vertex `v` has exactly one outgoing edge, to `next[v]`, carrying a value of the monoid `M`.
+///
+/// Answers `k`-step queries with a doubling table, and stores where each vertex enters its cycle.
+/// Values are folded in the order the edges are walked, so `M` need not be commutative.
+#[derive(Clone, Debug)]
+pub struct FunctionalGraph<M: ac_library::Monoid = super::monoid::Trivial> {
+    pub n: usize,
+    /// `jump[j][v]` is the vertex reached from `v` after `2^j` steps.
+    jump: Vec<Vec<usize>>,
+    /// `prod[j][v]` is the product of the values on those `2^j` edges.
+    prod: Vec<Vec<M::S>>,
+    /// Number of steps from each vertex until it first reaches a vertex on a cycle.
+    pub tail: Vec<usize>,
+    /// First vertex on a cycle reached from each vertex.
+    pub cycle_entry: Vec<usize>,
+    /// Length of the cycle eventually reached from each vertex.
+    pub cycle_len: Vec<usize>,
+}
+
+impl FunctionalGraph {
+    /// Creates a functional graph without values that answers queries with up to `max_k` steps.
+    ///
+    /// Time complexity is O(V log max_k)
+    pub fn new(next: Vec<usize>, max_k: u64) -> Self {
+        let n = next.len();
+        return Self::with_values(next, vec![(); n], max_k);
+    }
+}
+
+impl<M: ac_library::Monoid> FunctionalGraph<M> {
+    /// Creates a functional graph where the edge out of `v` carries `values[v]`,
+    /// answering queries with up to `max_k` steps.
+    ///
+    /// Time complexity is O(V log max_k)
+    pub fn with_values(next: Vec<usize>, values: Vec<M::S>, max_k: u64) -> Self {
+        let n = next.len();
+        assert_eq!(values.len(), n);
+        let (tail, cycle_entry, cycle_len) = Self::cycles(&next);
+
+        let levels = (64 - max_k.leading_zeros()) as usize;
+        let mut jump = vec![next];
+        let mut prod = vec![values];
+        for j in 1..levels {
+            let (jump_j, prod_j) = (0..n).map(|v| {
+                let mid = jump[j - 1][v];
+                (jump[j - 1][mid], M::binary_operation(&prod[j - 1][v], &prod[j - 1][mid]))
+            }).unzip();
+            jump.push(jump_j);
+            prod.push(prod_j);
+        }
+        return Self {n, jump, prod, tail, cycle_entry, cycle_len};
+    }
+
+    /// Returns `tail`, `cycle_entry` and `cycle_len` by walking from every vertex until a visited one.
+    fn cycles(next: &[usize]) -> (Vec<usize>, Vec<usize>, Vec<usize>) {
+        let n = next.len();
+        let mut tail = vec![0; n];
+        let mut cycle_entry = vec![0; n];
+        let mut cycle_len = vec![0; n];
+        // 0: unvisited, 1: on the current walk, 2: done
+        let mut state = vec![0u8; n];
+        let mut walk = vec![];
+        for start in 0..n {
+            let mut v = start;
+            while state[v] == 0 {
+                state[v] = 1;
+                walk.push(v);
+                v = next[v];
+            }
+            if state[v] == 1 {
+                let pos = walk.iter().position(|&x| x == v).unwrap();
+                let len = walk.len() - pos;
+                for &x in &walk[pos..] {
+                    cycle_entry[x] = x;
+                    cycle_len[x] = len;
+                    state[x] = 2;
+                }
+                walk.truncate(pos);
+            }
+            while let Some(x) = walk.pop() {
+                tail[x] = tail[next[x]] + 1;
+                cycle_entry[x] = cycle_entry[next[x]];
+                cycle_len[x] = cycle_len[next[x]];
+                state[x] = 2;
+            }
+        }
+        return (tail, cycle_entry, cycle_len);
+    }
+
+    /// Returns the vertex reached from `v` after `k` steps and the product of the values on the way.
+    ///
+    /// Time complexity is O(log k)
+    pub fn jump_fold(&self, mut v: usize, k: u64) -> (usize, M::S) {
+        assert!(k.checked_shr(self.jump.len() as u32).map_or(true, |r| r == 0), "k exceeds max_k");
+        let mut acc = M::identity();
+        for j in 0..self.jump.len() {
+            if k >> j & 1 == 1 {
+                acc = M::binary_operation(&acc, &self.prod[j][v]);
+                v = self.jump[j][v];
+            }
+        }
+        return (v, acc);
+    }
+
+    /// Returns the vertex reached from `v` after `k` steps.
+    ///
+    /// Time complexity is O(log k)
+    pub fn jump(&self, v: usize, k: u64) -> usize {
+        return self.jump_fold(v, k).0;
+    }
+
+    /// Returns the product of the values on the first `k` edges from `v`.
+    ///
+    /// Time complexity is O(log k)
+    pub fn fold(&self, v: usize, k: u64) -> M::S {
+        return self.jump_fold(v, k).1;
+    }
+
+    /// Returns whether `v` lies on a cycle.
+    pub fn on_cycle(&self, v: usize) -> bool {
+        return self.tail[v] == 0;
+    }
+}
+/* }}} */
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monoid::Affine;
+    use ac_library::{Additive, Monoid};
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn cycles() {
+        // 4 -> 3 -> 0 -> 1 -> 2 -> 0, 5 -> 5
+        let fg = FunctionalGraph::new(vec![1, 2, 0, 0, 3, 5], 10);
+        assert_eq!(fg.tail, vec![0, 0, 0, 1, 2, 0]);
+        assert_eq!(fg.cycle_entry, vec![0, 1, 2, 0, 0, 5]);
+        assert_eq!(fg.cycle_len, vec![3, 3, 3, 3, 3, 1]);
+        assert!(fg.on_cycle(1) && !fg.on_cycle(4));
+        assert_eq!(fg.jump(4, 0), 4);
+        assert_eq!(fg.jump(4, 3), 1);
+        assert_eq!(fg.jump(4, 10), 2);
+    }
+
+    #[test]
+    fn clone_and_debug() {
+        let fg = FunctionalGraph::new(vec![1, 0], 10);
+        let cloned = fg.clone();
+        assert_eq!(cloned.jump(0, 3), 1);
+        assert_eq!(format!("{:?}", cloned), format!("{:?}", fg));
+    }
+
+    #[test]
+    fn max_steps() {
+        let fg = FunctionalGraph::new(vec![1, 2, 0, 0, 3, 5], u64::MAX);
+        // 2^64 - 1 is a multiple of 3, so the walk from 4 ends 1 step past the entry of its cycle.
+        assert_eq!(fg.jump(4, u64::MAX), 1);
+        assert_eq!(fg.jump(0, u64::MAX), 0);
+        assert_eq!(fg.jump(5, u64::MAX), 5);
+        assert_eq!(fg.jump(4, 1 << 63), 0);
+    }
+
+    #[test]
+    fn sum_of_weights() {
+        let fg = FunctionalGraph::<Additive<u64>>::with_values(vec![1, 2, 0], vec![1, 10, 100], 1 << 40);
+        assert_eq!(fg.jump_fold(0, 4), (1, 112));
+        assert_eq!(fg.fold(1, 1 << 40), 111 * ((1 << 40) / 3) + 10);
+    }
+
+    #[test]
+    fn random() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+        for _ in 0..50 {
+            let n = rng.gen_range(1..12);
+            let next = (0..n).map(|_| rng.gen_range(0..n)).collect::<Vec<_>>();
+            let values = (0..n).map(|_| (rng.gen_range(-1..2), rng.gen_range(-3..4))).collect::<Vec<(i64, i64)>>();
+            let fg = FunctionalGraph::<Affine<i64>>::with_values(next.clone(), values.clone(), 40);
+            for v in 0..n {
+                let mut cur = v;
+                let mut acc = Affine::<i64>::identity();
+                let mut seen = vec![usize::MAX; n];
+                for k in 0..=40 {
+                    assert_eq!(fg.jump_fold(v, k), (cur, acc));
+                    if seen[cur] == usize::MAX {
+                        seen[cur] = k as usize;
+                    }
+                    acc = Affine::<i64>::binary_operation(&acc, &values[cur]);
+                    cur = next[cur];
+                }
+                // The first repeated vertex on the walk is the cycle entry.
+                let mut cur = v;
+                let mut steps = 0;
+                let mut first = vec![usize::MAX; n];
+                while first[cur] == usize::MAX {
+                    first[cur] = steps;
+                    cur = next[cur];
+                    steps += 1;
+                }
+                assert_eq!(fg.cycle_entry[v], cur);
+                assert_eq!(fg.tail[v], first[cur]);
+                assert_eq!(fg.cycle_len[v], steps - first[cur]);
+            }
+        }
+    }
+}
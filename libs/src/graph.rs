@@ -0,0 +1,1841 @@
+
+/* Weight {{{ */
+/// Edge weight of a `Graph`.
+///
+/// Implemented for integers, floats, `StaticModInt` and pairs of weights compared lexicographically.
+pub trait Weight: Copy + PartialEq + std::fmt::Debug {
+    fn zero() -> Self;
+    /// Value not less than any path length, used for unreachable vertices.
+    fn inf() -> Self;
+    fn plus(self, rhs: Self) -> Self;
+    fn compare(&self, other: &Self) -> std::cmp::Ordering;
+}
+
+macro_rules! impl_weight_for_int {
+    ($($t:ty),*) => {$(
+        impl Weight for $t {
+            fn zero() -> Self { 0 }
+            fn inf() -> Self { <$t>::MAX }
+            fn plus(self, rhs: Self) -> Self { self.saturating_add(rhs) }
+            fn compare(&self, other: &Self) -> std::cmp::Ordering { self.cmp(other) }
+        }
+    )*};
+}
+impl_weight_for_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_weight_for_float {
+    ($($t:ty),*) => {$(
+        impl Weight for $t {
+            fn zero() -> Self { 0.0 }
+            fn inf() -> Self { <$t>::INFINITY }
+            fn plus(self, rhs: Self) -> Self { self + rhs }
+            fn compare(&self, other: &Self) -> std::cmp::Ordering { self.total_cmp(other) }
+        }
+    )*};
+}
+impl_weight_for_float!(f32, f64);
+
+/// Ordered by the representative in `0..M`, with `M - 1` as infinity.
+impl<M: ac_library::Modulus> Weight for ac_library::StaticModInt<M> {
+    fn zero() -> Self { Self::raw(0) }
+    fn inf() -> Self { Self::raw(M::VALUE - 1) }
+    fn plus(self, rhs: Self) -> Self { self + rhs }
+    fn compare(&self, other: &Self) -> std::cmp::Ordering { self.val().cmp(&other.val()) }
+}
+
+impl<A: Weight, B: Weight> Weight for (A, B) {
+    fn zero() -> Self { (A::zero(), B::zero()) }
+    fn inf() -> Self { (A::inf(), B::inf()) }
+    fn plus(self, rhs: Self) -> Self { (self.0.plus(rhs.0), self.1.plus(rhs.1)) }
+    fn compare(&self, other: &Self) -> std::cmp::Ordering {
+        return self.0.compare(&other.0).then_with(|| self.1.compare(&other.1));
+    }
+}
+
+/// Heap entry popping the smallest weight first from `BinaryHeap`.
+pub(crate) struct MinByWeight<W>(pub(crate) W, pub(crate) usize);
+
+impl<W: Weight> PartialEq for MinByWeight<W> {
+    fn eq(&self, other: &Self) -> bool {
+        return self.cmp(other) == std::cmp::Ordering::Equal;
+    }
+}
+
+impl<W: Weight> Eq for MinByWeight<W> {}
+
+impl<W: Weight> PartialOrd for MinByWeight<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        return Some(self.cmp(other));
+    }
+}
+
+impl<W: Weight> Ord for MinByWeight<W> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        return other.0.compare(&self.0).then_with(|| other.1.cmp(&self.1));
+    }
+}
+/* }}} */
+
+
+/* Edge {{{ */
+/// Weighted directed edge, ordered by weight.
+/// `id` is the index returned by `Graph::add_edge`, so both directions of an undirected edge share it.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub struct Edge<W = i64> {
+    pub id: usize,
+    pub from: usize,
+    pub to: usize,
+    pub weight: W,
+}
+
+impl<W> Edge<W> {
+    pub fn new(id: usize, from: usize, to: usize, weight: W) -> Self {
+        return Self {id, from, to, weight};
+    }
+
+    /// Returns the same edge traversed from `to` to `from`.
+    pub fn rev(self) -> Self {
+        return Self {id: self.id, from: self.to, to: self.from, weight: self.weight};
+    }
+}
+
+impl<W: Weight> PartialOrd for Edge<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        return Some(self.weight.compare(&other.weight));
+    }
+}
+
+impl<W: Weight + Eq> Ord for Edge<W> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        return self.weight.compare(&other.weight);
+    }
+}
+/* }}} */
+
+
+/* ShortestPath {{{ */
+/// Single-source shortest paths with the last edge of a shortest path to each vertex.
+/// Distances are of type `D`, which differs from the edge weight `W` for `bfs`.
+#[derive(Clone, Debug)]
+pub struct ShortestPath<D = i64, W = D> {
+    /// Distance from the start vertex, or `None` if unreachable.
+    pub dist: Vec<Option<D>>,
+    /// Last edge of a shortest path to each vertex, oriented towards it, or `None` for the start and unreachable vertices.
+    pub prev: Vec<Option<Edge<W>>>,
+}
+
+impl<D: Copy, W: Copy> ShortestPath<D, W> {
+    fn new(n: usize, start: usize, zero: D) -> Self {
+        let mut dist = vec![None; n];
+        dist[start] = Some(zero);
+        return Self {dist, prev: vec![None; n]};
+    }
+
+    /// Returns the vertices and the edges of a shortest path from the start vertex to `target`,
+    /// or `None` if `target` is unreachable.
+    ///
+    /// Time complexity is O(path length)
+    pub fn path_to(&self, target: usize) -> Option<(Vec<usize>, Vec<Edge<W>>)> {
+        self.dist[target]?;
+        let mut edges = vec![];
+        let mut v = target;
+        while let Some(e) = self.prev[v] {
+            edges.push(e);
+            v = e.from;
+        }
+        edges.reverse();
+        let mut vertices = vec![v];
+        vertices.extend(edges.iter().map(|e| e.to));
+        return Some((vertices, edges));
+    }
+}
+/* }}} */
+
+
+/* shortest path loops {{{ */
+// Loops shared by `Adjacency` and `grid::Grid`, which give the edges leaving a vertex
+// by calling `out_edges(v, f)`, where `out_edges` calls `f(edge ID, to, weight)` for each of them.
+
+/// Loop of `Adjacency::bfs_with_prev` over the vertices `0..n`.
+///
+/// Time complexity is O(V + E)
+pub(crate) fn bfs_by_out_edges<W: Copy>(n: usize, start: usize, mut out_edges: impl FnMut(usize, &mut dyn FnMut(usize, usize, W))) -> ShortestPath<i64, W> {
+    let mut que = std::collections::VecDeque::new();
+    let mut res = ShortestPath::new(n, start, 0);
+    que.push_back(start);
+    while let Some(cur) = que.pop_front() {
+        let d = res.dist[cur].unwrap();
+        out_edges(cur, &mut |id, to, weight| {
+            if res.dist[to].is_none() {
+                res.dist[to] = Some(d + 1);
+                res.prev[to] = Some(Edge::new(id, cur, to, weight));
+                que.push_back(to);
+            }
+        });
+    }
+    return res;
+}
+
+/// Loop of `Adjacency::dijkstra_with_prev` over the vertices `0..n`.
+///
+/// Time complexity is O((V + E) log V)
+pub(crate) fn dijkstra_by_out_edges<W: Weight>(n: usize, start: usize, mut out_edges: impl FnMut(usize, &mut dyn FnMut(usize, usize, W))) -> ShortestPath<W> {
+    let mut que = std::collections::BinaryHeap::new();
+    let mut res = ShortestPath::new(n, start, W::zero());
+    que.push(MinByWeight(W::zero(), start));
+    while let Some(MinByWeight(d, cur)) = que.pop() {
+        if res.dist[cur] != Some(d) {
+            continue;
+        }
+        out_edges(cur, &mut |id, to, weight| {
+            let nd = d.plus(weight);
+            if res.dist[to].map_or(true, |x| x.compare(&nd).is_gt()) {
+                res.dist[to] = Some(nd);
+                res.prev[to] = Some(Edge::new(id, cur, to, weight));
+                que.push(MinByWeight(nd, to));
+            }
+        });
+    }
+    return res;
+}
+
+/// Loop of `Adjacency::zero_one_bfs` over the vertices `0..n`.
+///
+/// Time complexity is O(V + E)
+pub(crate) fn zero_one_bfs_by_out_edges<W: Weight>(n: usize, start: usize, mut out_edges: impl FnMut(usize, &mut dyn FnMut(usize, usize, W))) -> ShortestPath<W> {
+    let mut que = std::collections::VecDeque::new();
+    let mut res = ShortestPath::new(n, start, W::zero());
+    let mut done = vec![false; n];
+    que.push_back(start);
+    while let Some(cur) = que.pop_front() {
+        if std::mem::replace(&mut done[cur], true) {
+            continue;
+        }
+        let d = res.dist[cur].unwrap();
+        out_edges(cur, &mut |id, to, weight| {
+            let nd = d.plus(weight);
+            if res.dist[to].map_or(true, |x| x.compare(&nd).is_gt()) {
+                res.dist[to] = Some(nd);
+                res.prev[to] = Some(Edge::new(id, cur, to, weight));
+                if weight == W::zero() {
+                    que.push_front(to);
+                } else {
+                    que.push_back(to);
+                }
+            }
+        });
+    }
+    return res;
+}
+/* }}} */
+
+
+/* Dist {{{ */
+/// Distance in a graph that may contain negative cycles.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum Dist<W> {
+    /// A negative cycle lies on some path to the vertex, so it can be made arbitrarily short.
+    NegInf,
+    Finite(W),
+}
+/* }}} */
+
+
+/* Condensation {{{ */
+/// Strongly connected components of a `Graph` contracted into a DAG.
+#[derive(Clone, Debug)]
+pub struct Condensation<W = i64> {
+    /// Component index of each vertex, in topological order of the components.
+    pub ids: Vec<usize>,
+    /// Vertices of each component.
+    pub groups: Vec<Vec<usize>>,
+    /// Graph on the components with every edge between different components.
+    /// Parallel edges are kept.
+    pub dag: Graph<W>,
+}
+
+impl<W> Condensation<W> {
+    /// Returns `op` folded over `values` of the vertices in each component.
+    ///
+    /// Time complexity is O(V)
+    pub fn aggregate<T: Clone>(&self, values: &[T], op: impl Fn(T, T) -> T) -> Vec<T> {
+        return self.groups.iter().map(|group| {
+            let mut acc = values[group[0]].clone();
+            for &v in &group[1..] {
+                acc = op(acc, values[v].clone());
+            }
+            acc
+        }).collect();
+    }
+}
+/* }}} */
+
+
+/* TsortOrder {{{ */
+/// Which of the available vertices `Graph::tsort` outputs next.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum TsortOrder {
+    /// Lexicographically smallest order.
+    Smallest,
+    /// Lexicographically largest order.
+    Largest,
+    /// Vertices in the order they become available.
+    Fifo,
+}
+
+/// Vertices of a directed cycle, each with an edge to the next and the last with an edge to the first.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Cycle(pub Vec<usize>);
+
+/// Walk returned by `Adjacency::euler_trail`, where `edges[i]` goes from `vertices[i]` to `vertices[i + 1]`.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Trail<W = i64> {
+    pub vertices: Vec<usize>,
+    pub edges: Vec<Edge<W>>,
+}
+
+enum Frontier {
+    Smallest(std::collections::BinaryHeap<std::cmp::Reverse<usize>>),
+    Largest(std::collections::BinaryHeap<usize>),
+    Fifo(std::collections::VecDeque<usize>),
+}
+
+impl Frontier {
+    fn new(order: TsortOrder) -> Self {
+        return match order {
+            TsortOrder::Smallest => Frontier::Smallest(std::collections::BinaryHeap::new()),
+            TsortOrder::Largest => Frontier::Largest(std::collections::BinaryHeap::new()),
+            TsortOrder::Fifo => Frontier::Fifo(std::collections::VecDeque::new()),
+        };
+    }
+
+    fn push(&mut self, v: usize) {
+        match self {
+            Frontier::Smallest(que) => que.push(std::cmp::Reverse(v)),
+            Frontier::Largest(que) => que.push(v),
+            Frontier::Fifo(que) => que.push_back(v),
+        }
+    }
+
+    fn pop(&mut self) -> Option<usize> {
+        return match self {
+            Frontier::Smallest(que) => que.pop().map(|std::cmp::Reverse(v)| v),
+            Frontier::Largest(que) => que.pop(),
+            Frontier::Fifo(que) => que.pop_front(),
+        };
+    }
+}
+/* }}} */
+
+
+/* MstResult {{{ */
+/// Minimum spanning forest, treating every edge as undirected.
+/// Ties between equal weights are broken by edge ID, so every algorithm returns the same forest.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct MstResult<W = i64> {
+    pub weight: W,
+    /// IDs of the chosen edges in the order they were chosen.
+    pub edges: Vec<usize>,
+    /// Number of connected components, which is 1 if the forest is a spanning tree.
+    pub components: usize,
+}
+
+/// Whether an edge belongs to the minimum spanning forests, as classified by `Adjacency::mst_membership`.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum MstMembership {
+    /// In every minimum spanning forest.
+    Always,
+    /// In some but not all minimum spanning forests.
+    Sometimes,
+    /// In no minimum spanning forest.
+    Never,
+}
+/* }}} */
+
+
+/* Graph {{{ */
+/// Weighted directed graph stored as adjacency lists.
+/// Undirected graphs add each edge with `add_undirected_edge`, which stores it in both directions under one ID.
+#[derive(Clone, Debug)]
+pub struct Graph<W = i64> {
+    /// `(edge ID, to, weight)` for each edge leaving each vertex.
+    pub g: Vec<Vec<(usize, usize, W)>>,
+    pub n: usize,
+    /// Edges indexed by ID, oriented as they were added.
+    pub edges: Vec<Edge<W>>,
+}
+
+impl<W: Weight> Graph<W> {
+    pub fn new(n: usize) -> Self {
+        return Self {g: vec![vec![]; n], n, edges: vec![]};
+    }
+
+
+    /* add_edge {{{ */
+    /// Adds a directed edge and returns its ID, which counts up from 0.
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: W) -> usize {
+        let id = self.edges.len();
+        self.edges.push(Edge::new(id, from, to, weight));
+        self.g[from].push((id, to, weight));
+        return id;
+    }
+    /* }}} */
+
+
+    /* add_undirected_edge {{{ */
+    /// Adds an edge usable in both directions and returns its ID.
+    /// A self-loop is stored once.
+    pub fn add_undirected_edge(&mut self, u: usize, v: usize, weight: W) -> usize {
+        let id = self.add_edge(u, v, weight);
+        if u != v {
+            self.g[v].push((id, u, weight));
+        }
+        return id;
+    }
+    /* }}} */
+}
+
+impl<W: Weight> Adjacency for Graph<W> {
+    type W = W;
+
+    fn num_vertices(&self) -> usize {
+        return self.n;
+    }
+
+    fn out_edges(&self, v: usize) -> &[(usize, usize, W)] {
+        return &self.g[v];
+    }
+
+    fn edge_list(&self) -> &[Edge<W>] {
+        return &self.edges;
+    }
+}
+/* }}} */
+
+
+/* CsrGraph {{{ */
+/// Static weighted directed graph storing all adjacency lists in one array (compressed sparse row).
+/// Traverses large graphs faster than `Graph`, but edges cannot be added after construction.
+#[derive(Clone, Debug)]
+pub struct CsrGraph<W = i64> {
+    pub n: usize,
+    /// The adjacency list of `v` is `adj[start[v]..start[v + 1]]`.
+    start: Vec<usize>,
+    adj: Vec<(usize, usize, W)>,
+    /// Edges indexed by ID, oriented as they were given.
+    pub edges: Vec<Edge<W>>,
+}
+
+impl<W: Weight> CsrGraph<W> {
+    /// Builds the graph with directed edges `(from, to, weight)`, whose IDs are their indices.
+    ///
+    /// Time complexity is O(V + E)
+    pub fn new(n: usize, edges: &[(usize, usize, W)]) -> Self {
+        return Self::build(n, edges, false);
+    }
+
+    /// Same as `new`, but each edge is usable in both directions as with `Graph::add_undirected_edge`.
+    ///
+    /// Time complexity is O(V + E)
+    pub fn undirected(n: usize, edges: &[(usize, usize, W)]) -> Self {
+        return Self::build(n, edges, true);
+    }
+
+    fn build(n: usize, edges: &[(usize, usize, W)], undirected: bool) -> Self {
+        let mut start = vec![0; n + 1];
+        for &(from, to, _) in edges {
+            start[from + 1] += 1;
+            if undirected && from != to {
+                start[to + 1] += 1;
+            }
+        }
+        for v in 0..n {
+            start[v + 1] += start[v];
+        }
+        let mut adj = vec![(0, 0, W::zero()); start[n]];
+        let mut next = start.clone();
+        for (id, &(from, to, weight)) in edges.iter().enumerate() {
+            adj[next[from]] = (id, to, weight);
+            next[from] += 1;
+            if undirected && from != to {
+                adj[next[to]] = (id, from, weight);
+                next[to] += 1;
+            }
+        }
+        let edges = edges.iter().enumerate().map(|(id, &(from, to, weight))| Edge::new(id, from, to, weight)).collect();
+        return Self {n, start, adj, edges};
+    }
+}
+
+impl<W: Weight> From<&Graph<W>> for CsrGraph<W> {
+    /// Copies `g` with the same edge IDs and adjacency order.
+    fn from(g: &Graph<W>) -> Self {
+        let mut start = vec![0; g.n + 1];
+        for v in 0..g.n {
+            start[v + 1] = start[v] + g.g[v].len();
+        }
+        return Self {n: g.n, start, adj: g.g.concat(), edges: g.edges.clone()};
+    }
+}
+
+impl<W: Weight> Adjacency for CsrGraph<W> {
+    type W = W;
+
+    fn num_vertices(&self) -> usize {
+        return self.n;
+    }
+
+    fn out_edges(&self, v: usize) -> &[(usize, usize, W)] {
+        return &self.adj[self.start[v]..self.start[v + 1]];
+    }
+
+    fn edge_list(&self) -> &[Edge<W>] {
+        return &self.edges;
+    }
+}
+/* }}} */
+
+
+/* Adjacency {{{ */
+/// Read access to a weighted directed graph, shared by `Graph` and `CsrGraph`.
+///
+/// The algorithms are default methods, so bring the trait into scope to call them, e.g. `use graph::{Adjacency, Graph};`.
+/// Implementing the three required methods runs them on another representation.
+pub trait Adjacency {
+
+    type W: Weight;
+
+
+    fn num_vertices(&self) -> usize;
+    /// `(edge ID, to, weight)` for each edge leaving `v`.
+    fn out_edges(&self, v: usize) -> &[(usize, usize, Self::W)];
+    /// Edges indexed by ID.
+    fn edge_list(&self) -> &[Edge<Self::W>];
+
+
+    /* bfs {{{ */
+    /// Returns the number of edges on the shortest path from the start vertex to all other vertices.
+    /// Unreachable vertices are `None`.
+    ///
+    /// Time complexity is O(V + E)
+    fn bfs(&self, start: usize) -> Vec<Option<i64>> {
+        return self.bfs_with_prev(start).dist;
+    }
+    /* }}} */
+
+
+    /* bfs_with_prev {{{ */
+    /// Same as `bfs`, but also returns the last edge of a shortest path to each vertex.
+    ///
+    /// Time complexity is O(V + E)
+    fn bfs_with_prev(&self, start: usize) -> ShortestPath<i64, Self::W> {
+        return bfs_by_out_edges(self.num_vertices(), start, |v, f| self.out_edges(v).iter().for_each(|&(id, to, weight)| f(id, to, weight)));
+    }
+    /* }}} */
+
+
+    /* dijkstra {{{ */
+    /// Returns the shortest path from the start vertex to all other vertices.
+    /// Unreachable vertices are `None`.
+    /// Weights must be non-negative.
+    ///
+    /// Time complexity is O((V + E) log V)
+    fn dijkstra(&self, start: usize) -> Vec<Option<Self::W>> {
+        return self.dijkstra_with_prev(start).dist;
+    }
+    /* }}} */
+
+
+    /* dijkstra_with_prev {{{ */
+    /// Same as `dijkstra`, but also returns the last edge of a shortest path to each vertex.
+    ///
+    /// Time complexity is O((V + E) log V)
+    fn dijkstra_with_prev(&self, start: usize) -> ShortestPath<Self::W> {
+        return dijkstra_by_out_edges(self.num_vertices(), start, |v, f| self.out_edges(v).iter().for_each(|&(id, to, weight)| f(id, to, weight)));
+    }
+    /* }}} */
+
+
+    /* zero_one_bfs {{{ */
+    /// Same as `dijkstra_with_prev`, for graphs whose weights are all 0 or 1.
+    ///
+    /// Time complexity is O(V + E)
+    fn zero_one_bfs(&self, start: usize) -> ShortestPath<Self::W> {
+        return zero_one_bfs_by_out_edges(self.num_vertices(), start, |v, f| self.out_edges(v).iter().for_each(|&(id, to, weight)| f(id, to, weight)));
+    }
+    /* }}} */
+
+
+    /* dial {{{ */
+    /// Same as `dijkstra_with_prev`, for graphs with small non-negative integer weights.
+    /// Vertices are taken from C + 1 buckets indexed by distance, where C is the maximum weight.
+    ///
+    /// Time complexity is O(V + E + D), where D is the largest finite distance
+    fn dial(&self, start: usize) -> ShortestPath<Self::W>
+    where
+        Self::W: TryInto<usize>,
+    {
+        let to_usize = |weight: Self::W| -> usize {
+            let Ok(w) = weight.try_into() else { panic!("weights must be non-negative: {:?}", weight) };
+            return w;
+        };
+        let max_weight = self.edge_list().iter().map(|e| to_usize(e.weight)).max().unwrap_or(0);
+        let mut buckets = vec![vec![]; max_weight + 1];
+        let mut res = ShortestPath::new(self.num_vertices(), start, Self::W::zero());
+        let mut dist = vec![usize::MAX; self.num_vertices()];
+        dist[start] = 0;
+        buckets[0].push(start);
+        let mut pending = 1;
+        let mut d = 0;
+        while pending > 0 {
+            while let Some(cur) = buckets[d % (max_weight + 1)].pop() {
+                pending -= 1;
+                if dist[cur] != d {
+                    continue;
+                }
+                let dw = res.dist[cur].unwrap();
+                self.out_edges(cur).iter().for_each(|&(id, to, weight)| {
+                    let nd = d + to_usize(weight);
+                    if nd < dist[to] {
+                        dist[to] = nd;
+                        res.dist[to] = Some(dw.plus(weight));
+                        res.prev[to] = Some(Edge::new(id, cur, to, weight));
+                        buckets[nd % (max_weight + 1)].push(to);
+                        pending += 1;
+                    }
+                });
+            }
+            d += 1;
+        }
+        return res;
+    }
+    /* }}} */
+
+
+    /* floyd_warshall {{{ */
+    /// Returns the shortest path between all pairs of vertices.
+    /// Unreachable pairs are `None`.
+    /// The graph has a negative cycle through `i` if and only if `dist[i][i]` is negative.
+    ///
+    /// Time complexity is O(V^3)
+    fn floyd_warshall(&self) -> Vec<Vec<Option<Self::W>>> {
+        let mut dist = vec![vec![None; self.num_vertices()]; self.num_vertices()];
+        for i in 0..self.num_vertices() {
+            dist[i][i] = Some(Self::W::zero());
+        }
+        for from in 0..self.num_vertices() {
+            self.out_edges(from).iter().for_each(|&(_, to, weight)| {
+                if dist[from][to].map_or(true, |d: Self::W| d.compare(&weight).is_gt()) {
+                    dist[from][to] = Some(weight);
+                }
+            });
+        }
+        for k in 0..self.num_vertices() {
+            for i in 0..self.num_vertices() {
+                let Some(dik) = dist[i][k] else { continue };
+                for j in 0..self.num_vertices() {
+                    let Some(dkj) = dist[k][j] else { continue };
+                    let nd = dik.plus(dkj);
+                    if dist[i][j].map_or(true, |d| d.compare(&nd).is_gt()) {
+                        dist[i][j] = Some(nd);
+                    }
+                }
+            }
+        }
+        return dist;
+    }
+    /* }}} */
+
+
+    /* bellman_ford {{{ */
+    /// Returns the shortest path from the start vertex to all other vertices, allowing negative weights.
+    /// Unreachable vertices are `None`, and vertices reachable through a negative cycle are `Dist::NegInf`.
+    ///
+    /// Time complexity is O(VE)
+    fn bellman_ford(&self, start: usize) -> Vec<Option<Dist<Self::W>>> {
+        let mut dist = vec![None; self.num_vertices()];
+        let mut neg = vec![false; self.num_vertices()];
+        dist[start] = Some(Self::W::zero());
+        // After V - 1 rounds only vertices affected by a negative cycle are still relaxed,
+        // and another V rounds spread the mark to everything reachable from them.
+        for round in 0..2 * self.num_vertices() {
+            let mut updated = false;
+            for from in 0..self.num_vertices() {
+                let Some(d) = dist[from] else { continue };
+                self.out_edges(from).iter().for_each(|&(_, to, weight)| {
+                    let nd = d.plus(weight);
+                    if dist[to].map_or(true, |x: Self::W| x.compare(&nd).is_gt()) {
+                        dist[to] = Some(nd);
+                        updated = true;
+                        if round + 1 >= self.num_vertices() {
+                            neg[to] = true;
+                        }
+                    }
+                    if neg[from] {
+                        neg[to] = true;
+                    }
+                });
+            }
+            if !updated {
+                break;
+            }
+        }
+        return (0..self.num_vertices()).map(|v| if neg[v] { Some(Dist::NegInf) } else { dist[v].map(Dist::Finite) }).collect();
+    }
+    /* }}} */
+
+
+    /* spfa {{{ */
+    /// Same as `bellman_ford`, but only relaxes edges out of vertices whose distance changed.
+    /// Usually much faster than `bellman_ford` on random graphs.
+    ///
+    /// Time complexity is O(VE)
+    fn spfa(&self, start: usize) -> Vec<Option<Dist<Self::W>>> {
+        let mut dist = vec![None; self.num_vertices()];
+        // Number of edges on the current shortest path, which reaches V only through a negative cycle.
+        let mut len = vec![0; self.num_vertices()];
+        let mut neg = vec![false; self.num_vertices()];
+        let mut in_que = vec![false; self.num_vertices()];
+        let mut que = std::collections::VecDeque::new();
+        dist[start] = Some(Self::W::zero());
+        que.push_back(start);
+        in_que[start] = true;
+        while let Some(cur) = que.pop_front() {
+            in_que[cur] = false;
+            if neg[cur] {
+                continue;
+            }
+            let d = dist[cur].unwrap();
+            for &(_, to, weight) in self.out_edges(cur) {
+                let nd = d.plus(weight);
+                if dist[to].map_or(true, |x: Self::W| x.compare(&nd).is_gt()) {
+                    dist[to] = Some(nd);
+                    len[to] = len[cur] + 1;
+                    if len[to] >= self.num_vertices() {
+                        neg[to] = true;
+                    }
+                    if !in_que[to] {
+                        que.push_back(to);
+                        in_que[to] = true;
+                    }
+                }
+            }
+        }
+        let mut stack = (0..self.num_vertices()).filter(|&v| neg[v]).collect::<Vec<_>>();
+        while let Some(cur) = stack.pop() {
+            for &(_, to, _) in self.out_edges(cur) {
+                if !neg[to] {
+                    neg[to] = true;
+                    stack.push(to);
+                }
+            }
+        }
+        return (0..self.num_vertices()).map(|v| if neg[v] { Some(Dist::NegInf) } else { dist[v].map(Dist::Finite) }).collect();
+    }
+    /* }}} */
+
+
+    /* negative_cycle {{{ */
+    /// Returns the edges of a negative cycle in order, or `None` if there is none.
+    /// The cycle may be anywhere in the graph, not only reachable from a particular vertex.
+    ///
+    /// Time complexity is O(VE)
+    fn negative_cycle(&self) -> Option<Vec<Edge<Self::W>>> {
+        // Bellman-Ford from a virtual vertex with a zero-weight edge to every vertex.
+        let mut dist = vec![Self::W::zero(); self.num_vertices()];
+        let mut prev: Vec<Option<Edge<Self::W>>> = vec![None; self.num_vertices()];
+        let mut last = None;
+        for _ in 0..self.num_vertices() {
+            last = None;
+            for from in 0..self.num_vertices() {
+                for &(id, to, weight) in self.out_edges(from) {
+                    let nd = dist[from].plus(weight);
+                    if dist[to].compare(&nd).is_gt() {
+                        dist[to] = nd;
+                        prev[to] = Some(Edge::new(id, from, to, weight));
+                        last = Some(to);
+                    }
+                }
+            }
+            last?;
+        }
+        // Relaxed in the V-th round, so following V predecessor edges ends on the cycle.
+        let mut v = last?;
+        for _ in 0..self.num_vertices() {
+            v = prev[v].unwrap().from;
+        }
+        let mut cycle = vec![];
+        let mut cur = v;
+        loop {
+            let e = prev[cur].unwrap();
+            cycle.push(e);
+            cur = e.from;
+            if cur == v {
+                break;
+            }
+        }
+        cycle.reverse();
+        return Some(cycle);
+    }
+    /* }}} */
+
+
+    /* scc_ids {{{ */
+    /// Returns the number of strongly connected components and the component index of each vertex.
+    /// Components are numbered in topological order, so every edge goes to a component with an index not less than its own.
+    ///
+    /// Time complexity is O(V + E)
+    fn scc_ids(&self) -> (usize, Vec<usize>) {
+        // Tarjan's algorithm with an explicit call stack of (vertex, next edge index).
+        let mut ord = vec![usize::MAX; self.num_vertices()];
+        let mut low = vec![0; self.num_vertices()];
+        let mut ids = vec![usize::MAX; self.num_vertices()];
+        let mut visited = vec![];
+        let mut call = vec![];
+        let mut now = 0;
+        let mut k = 0;
+        for root in 0..self.num_vertices() {
+            if ord[root] != usize::MAX {
+                continue;
+            }
+            ord[root] = now;
+            low[root] = now;
+            now += 1;
+            visited.push(root);
+            call.push((root, 0));
+            while let Some(top) = call.last_mut() {
+                let v = top.0;
+                if top.1 < self.out_edges(v).len() {
+                    let to = self.out_edges(v)[top.1].1;
+                    top.1 += 1;
+                    if ord[to] == usize::MAX {
+                        ord[to] = now;
+                        low[to] = now;
+                        now += 1;
+                        visited.push(to);
+                        call.push((to, 0));
+                    } else if ids[to] == usize::MAX {
+                        low[v] = low[v].min(ord[to]);
+                    }
+                    continue;
+                }
+                call.pop();
+                if let Some(&(parent, _)) = call.last() {
+                    low[parent] = low[parent].min(low[v]);
+                }
+                if low[v] == ord[v] {
+                    while let Some(x) = visited.pop() {
+                        ids[x] = k;
+                        if x == v {
+                            break;
+                        }
+                    }
+                    k += 1;
+                }
+            }
+        }
+        // Tarjan finds sink components first.
+        ids.iter_mut().for_each(|id| *id = k - 1 - *id);
+        return (k, ids);
+    }
+    /* }}} */
+
+
+    /* scc {{{ */
+    /// Returns the strongly connected components of the graph in topological order.
+    /// The result is a vector of vectors, where each vector contains the vertices of a strongly connected component.
+    ///
+    /// Time complexity is O(V + E)
+    fn scc(&self) -> Vec<Vec<usize>> {
+        let (k, ids) = self.scc_ids();
+        let mut result = vec![vec![]; k];
+        for v in 0..self.num_vertices() {
+            result[ids[v]].push(v);
+        }
+        return result;
+    }
+    /* }}} */
+
+
+    /* condensation {{{ */
+    /// Returns the strongly connected components and the DAG obtained by contracting each of them.
+    ///
+    /// Time complexity is O(V + E)
+    fn condensation(&self) -> Condensation<Self::W> {
+        let (k, ids) = self.scc_ids();
+        let mut groups = vec![vec![]; k];
+        let mut dag = Graph::new(k);
+        for from in 0..self.num_vertices() {
+            groups[ids[from]].push(from);
+            self.out_edges(from).iter().for_each(|&(_, to, weight)| {
+                if ids[from] != ids[to] {
+                    dag.add_edge(ids[from], ids[to], weight);
+                }
+            });
+        }
+        return Condensation {ids, groups, dag};
+    }
+    /* }}} */
+
+
+    /* tsort {{{ */
+    /// Returns the topological sort of the graph, choosing among the available vertices by `order`.
+    /// Returns the vertices of a directed cycle in order if the graph is not a DAG.
+    ///
+    /// Time complexity is O(V + E) for `TsortOrder::Fifo` and O(V log V + E) otherwise
+    fn tsort(&self, order: TsortOrder) -> Result<Vec<usize>, Cycle> {
+        let mut deg = vec![0; self.num_vertices()];
+        for from in 0..self.num_vertices() {
+            self.out_edges(from).iter().for_each(|&(_, to, _weight)| deg[to] += 1);
+        }
+        let mut que = Frontier::new(order);
+        for i in 0..self.num_vertices() {
+            if deg[i] == 0 {
+                que.push(i);
+            }
+        }
+        let mut result = vec![];
+        while let Some(from) = que.pop() {
+            result.push(from);
+            self.out_edges(from).iter().for_each(|&(_, to, _weight)| {
+                deg[to] -= 1;
+                if deg[to] == 0 {
+                    que.push(to);
+                }
+            });
+        }
+        if result.len() == self.num_vertices() {
+            return Ok(result);
+        }
+
+        // Every remaining vertex has an incoming edge from another remaining vertex,
+        // so walking those edges backwards eventually repeats a vertex.
+        let mut pred = vec![usize::MAX; self.num_vertices()];
+        for from in 0..self.num_vertices() {
+            if deg[from] > 0 {
+                self.out_edges(from).iter().for_each(|&(_, to, _weight)| pred[to] = from);
+            }
+        }
+        let mut seen = vec![false; self.num_vertices()];
+        let mut v = (0..self.num_vertices()).find(|&v| deg[v] > 0).unwrap();
+        while !seen[v] {
+            seen[v] = true;
+            v = pred[v];
+        }
+        let mut cycle = vec![v];
+        let mut cur = pred[v];
+        while cur != v {
+            cycle.push(cur);
+            cur = pred[cur];
+        }
+        cycle.reverse();
+        return Err(Cycle(cycle));
+    }
+    /* }}} */
+
+
+    /* count_tsort {{{ */
+    /// Returns the number of topological orders of the graph, which is 0 if it has a cycle.
+    /// The count fits in `u64` for V <= 20.
+    ///
+    /// Time complexity is O(2^V V + E)
+    fn count_tsort(&self) -> u64 {
+        let mut pred = vec![0usize; self.num_vertices()];
+        for from in 0..self.num_vertices() {
+            self.out_edges(from).iter().for_each(|&(_, to, _weight)| pred[to] |= 1 << from);
+        }
+        // dp[mask] is the number of ways to order `mask` as a prefix of a topological order.
+        let mut dp = vec![0u64; 1 << self.num_vertices()];
+        dp[0] = 1;
+        for mask in 0..1usize << self.num_vertices() {
+            if dp[mask] == 0 {
+                continue;
+            }
+            for v in 0..self.num_vertices() {
+                if mask >> v & 1 == 0 && pred[v] & !mask == 0 {
+                    dp[mask | 1 << v] += dp[mask];
+                }
+            }
+        }
+        return dp[(1 << self.num_vertices()) - 1];
+    }
+    /* }}} */
+
+
+    /* kruskal {{{ */
+    /// Returns the edges of the minimum spanning tree of the graph.
+    /// Use `mst` for the total weight and the number of components.
+    ///
+    /// Time complexity is O(E log V)
+    fn kruskal(&self) -> Vec<Edge<Self::W>> {
+        return self.mst().edges.iter().map(|&id| self.edge_list()[id]).collect();
+    }
+    /* }}} */
+
+
+    /* mst {{{ */
+    /// Returns the minimum spanning forest with Kruskal's algorithm.
+    ///
+    /// Time complexity is O(E log V)
+    fn mst(&self) -> MstResult<Self::W> {
+        let n = self.num_vertices();
+        let mut es = self.edge_list().to_vec();
+        es.sort_by(|a: &Edge<Self::W>, b| a.weight.compare(&b.weight));
+        let mut uf = ac_library::Dsu::new(n);
+        let mut res = MstResult {weight: Self::W::zero(), edges: vec![], components: n};
+        for e in es {
+            if !uf.same(e.from, e.to) {
+                uf.merge(e.from, e.to);
+                res.weight = res.weight.plus(e.weight);
+                res.edges.push(e.id);
+                res.components -= 1;
+            }
+        }
+        return res;
+    }
+    /* }}} */
+
+
+    /* prim {{{ */
+    /// Same as `mst` with Prim's algorithm on an adjacency matrix, for dense graphs.
+    ///
+    /// Time complexity is O(V^2 + E)
+    fn prim(&self) -> MstResult<Self::W> {
+        let n = self.num_vertices();
+        let lighter = |a: (Self::W, usize), b: Option<(Self::W, usize)>| b.map_or(true, |b| a.0.compare(&b.0).then(a.1.cmp(&b.1)).is_lt());
+        // Lightest edge between each pair of vertices as (weight, ID).
+        let mut matrix = vec![vec![None; n]; n];
+        for e in self.edge_list() {
+            if e.from != e.to && lighter((e.weight, e.id), matrix[e.from][e.to]) {
+                matrix[e.from][e.to] = Some((e.weight, e.id));
+                matrix[e.to][e.from] = Some((e.weight, e.id));
+            }
+        }
+        let mut res = MstResult {weight: Self::W::zero(), edges: vec![], components: 0};
+        let mut used = vec![false; n];
+        // Lightest edge from the current tree to each vertex.
+        let mut best: Vec<Option<(Self::W, usize)>> = vec![None; n];
+        for _ in 0..n {
+            // A vertex without an edge to the tree is taken only when no other is left, and starts a new tree.
+            let v = (0..n).filter(|&v| !used[v]).reduce(|v, u| if best[u].map_or(false, |b| lighter(b, best[v])) { u } else { v }).unwrap();
+            used[v] = true;
+            match best[v] {
+                Some((weight, id)) => {
+                    res.weight = res.weight.plus(weight);
+                    res.edges.push(id);
+                }
+                None => res.components += 1,
+            }
+            for u in 0..n {
+                if let Some(x) = matrix[v][u] {
+                    if !used[u] && lighter(x, best[u]) {
+                        best[u] = Some(x);
+                    }
+                }
+            }
+        }
+        return res;
+    }
+    /* }}} */
+
+
+    /* boruvka {{{ */
+    /// Same as `mst` with Borůvka's algorithm, which adds the lightest edge leaving every component in each of O(log V) rounds.
+    ///
+    /// Time complexity is O(E log V)
+    fn boruvka(&self) -> MstResult<Self::W> {
+        let n = self.num_vertices();
+        let lighter = |a: &Edge<Self::W>, b: Option<Edge<Self::W>>| b.map_or(true, |b| a.weight.compare(&b.weight).then(a.id.cmp(&b.id)).is_lt());
+        let mut uf = ac_library::Dsu::new(n);
+        let mut res = MstResult {weight: Self::W::zero(), edges: vec![], components: n};
+        loop {
+            let mut lightest = vec![None; n];
+            for e in self.edge_list() {
+                let (a, b) = (uf.leader(e.from), uf.leader(e.to));
+                for c in [a, b] {
+                    if a != b && lighter(e, lightest[c]) {
+                        lightest[c] = Some(*e);
+                    }
+                }
+            }
+            let before = res.components;
+            for e in lightest.into_iter().flatten() {
+                if !uf.same(e.from, e.to) {
+                    uf.merge(e.from, e.to);
+                    res.weight = res.weight.plus(e.weight);
+                    res.edges.push(e.id);
+                    res.components -= 1;
+                }
+            }
+            if res.components == before {
+                return res;
+            }
+        }
+    }
+    /* }}} */
+
+
+    /* second_best_mst {{{ */
+    /// Returns the lightest spanning forest other than the one returned by `mst`, which may weigh the same,
+    /// or `None` if there is no other.
+    /// It exchanges one edge outside `mst` for the heaviest edge on the path between its endpoints.
+    ///
+    /// Time complexity is O(E log V)
+    fn second_best_mst(&self) -> Option<MstResult<Self::W>>
+    where
+        Self::W: std::ops::Sub<Output = Self::W>,
+    {
+        let n = self.num_vertices();
+        let edges = self.edge_list();
+        let mst = self.mst();
+        let mut in_mst = vec![false; edges.len()];
+        let mut adj = vec![vec![]; n];
+        for &id in &mst.edges {
+            in_mst[id] = true;
+            adj[edges[id].from].push((edges[id].to, id));
+            adj[edges[id].to].push((edges[id].from, id));
+        }
+        // `jump[j][v]` is the `2^j`-th ancestor of `v` with the heaviest edge on the way, or `usize::MAX` above a root.
+        let mut jump = vec![vec![(usize::MAX, usize::MAX); n]];
+        let mut depth = vec![0; n];
+        let mut seen = vec![false; n];
+        for root in 0..n {
+            if seen[root] {
+                continue;
+            }
+            seen[root] = true;
+            jump[0][root] = (root, usize::MAX);
+            let mut stack = vec![root];
+            while let Some(v) = stack.pop() {
+                for &(to, id) in &adj[v] {
+                    if !seen[to] {
+                        seen[to] = true;
+                        depth[to] = depth[v] + 1;
+                        jump[0][to] = (v, id);
+                        stack.push(to);
+                    }
+                }
+            }
+        }
+        let heavier = |a: usize, b: usize| if b == usize::MAX || (a != usize::MAX && edges[a].weight.compare(&edges[b].weight).is_ge()) { a } else { b };
+        while 1 << jump.len() < n {
+            let last = jump.last().unwrap();
+            let next = (0..n).map(|v| (last[last[v].0].0, heavier(last[v].1, last[last[v].0].1))).collect();
+            jump.push(next);
+        }
+        let path_max = |mut u: usize, mut v: usize| -> usize {
+            if depth[u] < depth[v] {
+                std::mem::swap(&mut u, &mut v);
+            }
+            let mut res = usize::MAX;
+            for j in 0..jump.len() {
+                if (depth[u] - depth[v]) >> j & 1 == 1 {
+                    res = heavier(res, jump[j][u].1);
+                    u = jump[j][u].0;
+                }
+            }
+            if u == v {
+                return res;
+            }
+            for j in (0..jump.len()).rev() {
+                if jump[j][u].0 != jump[j][v].0 {
+                    res = heavier(res, heavier(jump[j][u].1, jump[j][v].1));
+                    u = jump[j][u].0;
+                    v = jump[j][v].0;
+                }
+            }
+            return heavier(res, heavier(jump[0][u].1, jump[0][v].1));
+        };
+
+        // (weight, edge added, edge removed) of the best exchange.
+        let mut best: Option<(Self::W, usize, usize)> = None;
+        for e in edges {
+            if in_mst[e.id] || e.from == e.to {
+                continue;
+            }
+            let removed = path_max(e.from, e.to);
+            let weight = mst.weight.plus(e.weight) - edges[removed].weight;
+            if best.map_or(true, |(w, _, _)| weight.compare(&w).is_lt()) {
+                best = Some((weight, e.id, removed));
+            }
+        }
+        let (weight, added, removed) = best?;
+        let mut res = mst;
+        res.weight = weight;
+        res.edges.retain(|&id| id != removed);
+        res.edges.push(added);
+        return Some(res);
+    }
+    /* }}} */
+
+
+    /* mst_membership {{{ */
+    /// Returns whether each edge, indexed by ID, is in every, some or no minimum spanning forest.
+    /// Among the edges of one weight joining different components of the lighter edges,
+    /// every edge is in some forest and exactly the bridges between those components are in all of them.
+    ///
+    /// Time complexity is O(E log E)
+    fn mst_membership(&self) -> Vec<MstMembership> {
+        let n = self.num_vertices();
+        let mut es = self.edge_list().to_vec();
+        es.sort_by(|a: &Edge<Self::W>, b| a.weight.compare(&b.weight));
+        let mut res = vec![MstMembership::Never; es.len()];
+        let mut uf = ac_library::Dsu::new(n);
+        // Vertex of each component leader in the graph of the current weight, or `usize::MAX`.
+        let mut index = vec![usize::MAX; n];
+        let mut i = 0;
+        while i < es.len() {
+            let mut j = i;
+            while j < es.len() && es[j].weight.compare(&es[i].weight).is_eq() {
+                j += 1;
+            }
+            let mut leaders = vec![];
+            let mut pairs = vec![];
+            let mut ids = vec![];
+            for e in &es[i..j] {
+                let (a, b) = (uf.leader(e.from), uf.leader(e.to));
+                if a == b {
+                    continue;
+                }
+                for c in [a, b] {
+                    if index[c] == usize::MAX {
+                        index[c] = leaders.len();
+                        leaders.push(c);
+                    }
+                }
+                pairs.push((index[a], index[b]));
+                ids.push(e.id);
+                res[e.id] = MstMembership::Sometimes;
+            }
+            for &b in &super::lowlink::Lowlink::new(leaders.len(), &pairs).bridges {
+                res[ids[b]] = MstMembership::Always;
+            }
+            for c in leaders {
+                index[c] = usize::MAX;
+            }
+            for e in &es[i..j] {
+                uf.merge(e.from, e.to);
+            }
+            i = j;
+        }
+        return res;
+    }
+    /* }}} */
+
+
+    /* euler_trail {{{ */
+    /// Returns a trail using every edge exactly once, with the edges oriented as traversed,
+    /// or `None` if there is none because of the degrees or because the edges are not connected.
+    /// The trail is a circuit whenever one exists, starting from the smallest vertex with an edge.
+    /// The edges must be either all directed or all undirected; a graph without edges gives an empty trail.
+    ///
+    /// Time complexity is O(V + E)
+    fn euler_trail(&self) -> Option<Trail<Self::W>> {
+        let n = self.num_vertices();
+        let m = self.edge_list().len();
+        // Edges other than self-loops appear at both endpoints if undirected.
+        let mut count = vec![0; m];
+        for v in 0..n {
+            for &(id, _, _) in self.out_edges(v) {
+                count[id] += 1;
+            }
+        }
+        let undirected = count.contains(&2);
+        assert!(self.edge_list().iter().all(|e| e.from == e.to || count[e.id] == if undirected { 2 } else { 1 }), "directed and undirected edges are mixed");
+        if m == 0 {
+            return Some(Trail {vertices: vec![], edges: vec![]});
+        }
+
+        // Out-degree minus in-degree, or the degree modulo 2 if undirected.
+        let mut excess = vec![0_i64; n];
+        for e in self.edge_list() {
+            if undirected {
+                excess[e.from] ^= 1;
+                excess[e.to] ^= 1;
+            } else {
+                excess[e.from] += 1;
+                excess[e.to] -= 1;
+            }
+        }
+        let odd = (0..n).filter(|&v| excess[v] != 0).collect::<Vec<_>>();
+        let start = match odd[..] {
+            [] => self.edge_list().iter().map(|e| e.from.min(e.to)).min().unwrap(),
+            [u, v] if undirected => u.min(v),
+            [u, v] if excess[u] == 1 && excess[v] == -1 => u,
+            [u, v] if excess[u] == -1 && excess[v] == 1 => v,
+            _ => return None,
+        };
+
+        let mut used = vec![false; m];
+        let mut next = vec![0; n];
+        let mut edges = vec![];
+        // Walk unused edges from the top of the stack, and emit an edge once its head is stuck.
+        let mut stack = vec![(start, None)];
+        while let Some(&(v, e)) = stack.last() {
+            let out = self.out_edges(v);
+            while next[v] < out.len() && used[out[next[v]].0] {
+                next[v] += 1;
+            }
+            if next[v] == out.len() {
+                stack.pop();
+                edges.extend(e);
+            } else {
+                let (id, to, weight) = out[next[v]];
+                used[id] = true;
+                stack.push((to, Some(Edge::new(id, v, to, weight))));
+            }
+        }
+        if edges.len() < m {
+            return None;
+        }
+        edges.reverse();
+        let mut vertices = vec![start];
+        vertices.extend(edges.iter().map(|e| e.to));
+        return Some(Trail {vertices, edges});
+    }
+    /* }}} */
+
+
+    /* euler_circuit {{{ */
+    /// Same as `euler_trail`, but `None` unless the trail is a circuit.
+    ///
+    /// Time complexity is O(V + E)
+    fn euler_circuit(&self) -> Option<Trail<Self::W>> {
+        let trail = self.euler_trail()?;
+        return if trail.vertices.first() == trail.vertices.last() { Some(trail) } else { None };
+    }
+    /* }}} */
+
+
+    /* k_times_transition {{{ */
+    /// Returns the result of applying the transition function k times,
+    /// where the transition of `v` is the first edge added from `v`.
+    /// Build a `FunctionalGraph` instead to answer several queries.
+    ///
+    /// Time complexity is O(V log k)
+    fn k_times_transition(&self, k: u64) -> Vec<usize> {
+        let next = (0..self.num_vertices()).map(|v| self.out_edges(v)[0].1).collect();
+        let fg = super::functional_graph::FunctionalGraph::new(next, k);
+        return (0..self.num_vertices()).map(|v| fg.jump(v, k)).collect();
+    }
+    /* }}} */
+
+}
+/* }}} */
+
+
+
+/* de_bruijn {{{ */
+/// Returns a De Bruijn sequence of order `n` over the alphabet `0..k`,
+/// a cyclic sequence of length `k^n` in which every word of length `n` appears exactly once.
+/// Append its first `n - 1` symbols to make every word appear in the non-cyclic sequence.
+///
+/// It is an Euler circuit of the graph whose vertices are the words of length `n - 1`,
+/// with an edge labelled `c` from each word to the word with `c` appended and the first symbol dropped.
+///
+/// Time complexity is O(k^n)
+pub fn de_bruijn(k: usize, n: usize) -> Vec<usize> {
+    assert!(k >= 1 && n >= 1);
+    let words = k.pow(n as u32 - 1);
+    let mut g = Graph::new(words);
+    for v in 0..words {
+        for c in 0..k {
+            g.add_edge(v, (v * k + c) % words, c);
+        }
+    }
+    return g.euler_circuit().unwrap().edges.iter().map(|e| e.weight).collect();
+}
+/* }}} */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Graph {
+        let mut g = Graph::new(5);
+        for &(from, to, weight) in &[(0, 1, 4), (0, 2, 1), (2, 1, 2), (1, 3, 5), (2, 3, 8)] {
+            g.add_edge(from, to, weight);
+        }
+        g
+    }
+
+    #[test]
+    fn bfs() {
+        assert_eq!(sample().bfs(0), vec![Some(0), Some(1), Some(1), Some(2), None]);
+        assert_eq!(sample().bfs(3), vec![None, None, None, Some(0), None]);
+    }
+
+    #[test]
+    fn bfs_path() {
+        let sp = sample().bfs_with_prev(0);
+        let (vertices, edges) = sp.path_to(3).unwrap();
+        assert_eq!(vertices, vec![0, 1, 3]);
+        assert_eq!(edges, vec![Edge::new(0, 0, 1, 4), Edge::new(3, 1, 3, 5)]);
+        assert_eq!(sp.path_to(0), Some((vec![0], vec![])));
+        assert_eq!(sp.path_to(4), None);
+    }
+
+    #[test]
+    fn edge_ids() {
+        // Two parallel cables between 0 and 1, and one between 1 and 2.
+        let mut g = Graph::new(3);
+        assert_eq!(g.add_undirected_edge(0, 1, 5), 0);
+        assert_eq!(g.add_undirected_edge(1, 0, 2), 1);
+        assert_eq!(g.add_undirected_edge(2, 1, 1), 2);
+        assert_eq!(g.add_edge(2, 2, 0), 3);
+        assert_eq!(g.g[1], vec![(0, 0, 5), (1, 0, 2), (2, 2, 1)]);
+        assert_eq!(g.g[2], vec![(2, 1, 1), (3, 2, 0)]);
+
+        let sp = g.dijkstra_with_prev(0);
+        let (_, edges) = sp.path_to(2).unwrap();
+        assert_eq!(edges.iter().map(|e| e.id).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(edges[0], g.edges[1].rev());
+        assert_eq!(edges[1], g.edges[2].rev());
+
+        let mut mst = g.kruskal().iter().map(|e| e.id).collect::<Vec<_>>();
+        mst.sort();
+        assert_eq!(mst, vec![1, 2]);
+    }
+
+    #[test]
+    fn csr() {
+        use rand::{Rng, SeedableRng};
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(2);
+        for _ in 0..50 {
+            let n = rng.gen_range(1..10);
+            let edges = (0..rng.gen_range(0..20)).map(|_| (rng.gen_range(0..n), rng.gen_range(0..n), rng.gen_range(0..10))).collect::<Vec<_>>();
+            for undirected in [false, true] {
+                let mut g = Graph::new(n);
+                for &(from, to, weight) in &edges {
+                    if undirected {
+                        g.add_undirected_edge(from, to, weight);
+                    } else {
+                        g.add_edge(from, to, weight);
+                    }
+                }
+                let csr = if undirected { CsrGraph::undirected(n, &edges) } else { CsrGraph::new(n, &edges) };
+                for v in 0..n {
+                    assert_eq!(csr.out_edges(v), g.out_edges(v));
+                    assert_eq!(CsrGraph::from(&g).out_edges(v), g.out_edges(v));
+                }
+                let start = rng.gen_range(0..n);
+                assert_eq!(csr.bfs(start), g.bfs(start));
+                assert_eq!(csr.dijkstra_with_prev(start).prev, g.dijkstra_with_prev(start).prev);
+                assert_eq!(csr.scc(), g.scc());
+                assert_eq!(csr.tsort(TsortOrder::Smallest), g.tsort(TsortOrder::Smallest));
+                assert_eq!(csr.kruskal(), g.kruskal());
+            }
+        }
+    }
+
+    #[test]
+    fn dijkstra() {
+        assert_eq!(sample().dijkstra(0), vec![Some(0), Some(3), Some(1), Some(8), None]);
+        assert_eq!(sample().dijkstra(2), vec![None, Some(2), Some(0), Some(7), None]);
+    }
+
+    #[test]
+    fn dijkstra_path() {
+        let sp = sample().dijkstra_with_prev(0);
+        let (vertices, edges) = sp.path_to(3).unwrap();
+        assert_eq!(vertices, vec![0, 2, 1, 3]);
+        assert_eq!(edges.iter().map(|e| e.weight).sum::<i64>(), sp.dist[3].unwrap());
+        assert_eq!(sp.prev[1], Some(Edge::new(2, 2, 1, 2)));
+        assert_eq!(sp.prev[0], None);
+        assert_eq!(sp.path_to(4), None);
+    }
+
+    #[test]
+    fn floyd_warshall() {
+        let dist = sample().floyd_warshall();
+        assert_eq!(dist[0], vec![Some(0), Some(3), Some(1), Some(8), None]);
+        assert_eq!(dist[2][3], Some(7));
+        assert_eq!(dist[3][0], None);
+        for start in 0..5 {
+            assert_eq!(dist[start], sample().dijkstra(start));
+        }
+    }
+
+    #[test]
+    fn zero_one_bfs() {
+        let mut g = Graph::new(5);
+        for &(from, to, weight) in &[(0, 1, 1), (0, 2, 0), (2, 3, 1), (1, 3, 0), (3, 4, 0), (2, 1, 1)] {
+            g.add_edge(from, to, weight);
+        }
+        let sp = g.zero_one_bfs(0);
+        assert_eq!(sp.dist, g.dijkstra(0));
+        assert_eq!(sp.dist, vec![Some(0), Some(1), Some(0), Some(1), Some(1)]);
+        assert_eq!(sp.path_to(4).unwrap().0.len(), 4);
+    }
+
+    #[test]
+    fn dial() {
+        let sp = sample().dial(0);
+        assert_eq!(sp.dist, sample().dijkstra(0));
+        assert_eq!(sp.path_to(3).unwrap().0, vec![0, 2, 1, 3]);
+
+        use rand::{Rng, SeedableRng};
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(1);
+        for _ in 0..100 {
+            let n = rng.gen_range(1..10);
+            let mut g = Graph::<u32>::new(n);
+            for _ in 0..rng.gen_range(0..20) {
+                g.add_edge(rng.gen_range(0..n), rng.gen_range(0..n), rng.gen_range(0..2));
+            }
+            assert_eq!(g.zero_one_bfs(0).dist, g.dijkstra(0));
+            for _ in 0..5 {
+                g.add_edge(rng.gen_range(0..n), rng.gen_range(0..n), rng.gen_range(0..6));
+            }
+            let sp = g.dial(0);
+            assert_eq!(sp.dist, g.dijkstra(0));
+            for v in 0..n {
+                if let Some((_, edges)) = sp.path_to(v) {
+                    assert_eq!(edges.iter().map(|e| e.weight).sum::<u32>(), sp.dist[v].unwrap());
+                }
+            }
+        }
+    }
+
+    fn negative() -> Graph {
+        // 1 -> 2 -> 3 -> 1 weighs -1, 4 hangs off the cycle, and 5 is only reachable from 0.
+        let mut g = Graph::new(7);
+        for &(from, to, weight) in &[(0, 1, 1), (1, 2, 2), (2, 3, -4), (3, 1, 1), (3, 4, 0), (0, 5, -3), (5, 4, 10)] {
+            g.add_edge(from, to, weight);
+        }
+        g
+    }
+
+    #[test]
+    fn bellman_ford() {
+        use Dist::*;
+        assert_eq!(sample().bellman_ford(0), vec![Some(Finite(0)), Some(Finite(3)), Some(Finite(1)), Some(Finite(8)), None]);
+        assert_eq!(
+            negative().bellman_ford(0),
+            vec![Some(Finite(0)), Some(NegInf), Some(NegInf), Some(NegInf), Some(NegInf), Some(Finite(-3)), None],
+        );
+        assert_eq!(negative().bellman_ford(5), vec![None, None, None, None, Some(Finite(10)), Some(Finite(0)), None]);
+        assert_eq!(negative().spfa(0), negative().bellman_ford(0));
+    }
+
+    #[test]
+    fn spfa_random() {
+        use rand::{Rng, SeedableRng};
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+        for _ in 0..200 {
+            let n = rng.gen_range(1..8);
+            let mut g = Graph::new(n);
+            for _ in 0..rng.gen_range(0..15) {
+                g.add_edge(rng.gen_range(0..n), rng.gen_range(0..n), rng.gen_range(-3..10));
+            }
+            let start = rng.gen_range(0..n);
+            let dist = g.bellman_ford(start);
+            assert_eq!(g.spfa(start), dist);
+            let has_cycle = g.floyd_warshall().iter().enumerate().any(|(i, d)| d[i].map_or(false, |x| x < 0));
+            match g.negative_cycle() {
+                Some(cycle) => {
+                    assert!(cycle.iter().map(|e| e.weight).sum::<i64>() < 0);
+                    for i in 0..cycle.len() {
+                        assert_eq!(cycle[i].to, cycle[(i + 1) % cycle.len()].from);
+                    }
+                    assert_eq!(g.edges[cycle[0].id], cycle[0]);
+                }
+                None => assert!(!has_cycle),
+            }
+            assert_eq!(g.negative_cycle().is_some(), has_cycle);
+            if !has_cycle {
+                assert!(dist.iter().all(|d| d != &Some(Dist::NegInf)));
+            }
+        }
+    }
+
+    #[test]
+    fn negative_cycle() {
+        let cycle = negative().negative_cycle().unwrap();
+        let mut vertices = cycle.iter().map(|e| e.from).collect::<Vec<_>>();
+        vertices.sort();
+        assert_eq!(vertices, vec![1, 2, 3]);
+        assert_eq!(cycle.iter().map(|e| e.weight).sum::<i64>(), -1);
+        assert_eq!(sample().negative_cycle(), None);
+    }
+
+    #[test]
+    fn float_weights() {
+        let mut g = Graph::<f64>::new(3);
+        g.add_edge(0, 1, 0.5);
+        g.add_edge(1, 2, 0.25);
+        g.add_edge(0, 2, 1.0);
+        assert_eq!(g.dijkstra(0), vec![Some(0.0), Some(0.5), Some(0.75)]);
+        assert_eq!(g.floyd_warshall()[0][2], Some(0.75));
+        assert_eq!(g.kruskal().iter().map(|e| e.weight).sum::<f64>(), 0.75);
+    }
+
+    #[test]
+    fn tuple_weights() {
+        // Minimize the cost first and then the number of edges.
+        let mut g = Graph::<(i64, i64)>::new(4);
+        g.add_edge(0, 1, (1, 1));
+        g.add_edge(1, 2, (1, 1));
+        g.add_edge(2, 3, (1, 1));
+        g.add_edge(0, 3, (4, 1));
+        g.add_edge(0, 2, (2, 1));
+        let sp = g.dijkstra_with_prev(0);
+        assert_eq!(sp.dist[3], Some((3, 2)));
+        assert_eq!(sp.path_to(3).unwrap().0, vec![0, 2, 3]);
+    }
+
+    #[test]
+    fn modint_weights() {
+        use ac_library::ModInt998244353 as Mint;
+        let mut g = Graph::<Mint>::new(3);
+        g.add_edge(0, 1, Mint::new(998244352));
+        g.add_edge(1, 2, Mint::new(3));
+        assert_eq!(g.floyd_warshall()[0][2], Some(Mint::new(2)));
+        assert_eq!(g.bfs(0), vec![Some(0), Some(1), Some(2)]);
+    }
+
+    #[test]
+    fn scc() {
+        let mut g = Graph::new(6);
+        for &(from, to) in &[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 3), (5, 4)] {
+            g.add_edge(from, to, 1);
+        }
+        let comps = g.scc();
+        assert_eq!(comps.len(), 3);
+        assert_eq!(comps[2], vec![3, 4]);
+        assert!(comps[..2].contains(&vec![0, 1, 2]) && comps[..2].contains(&vec![5]));
+        assert!(Graph::<i64>::new(0).scc().is_empty());
+    }
+
+    #[test]
+    fn scc_deep() {
+        // A single path would overflow the stack with a recursive DFS.
+        let n = 300_000;
+        let mut g = Graph::new(n);
+        for v in 1..n {
+            g.add_edge(v, v - 1, 1);
+        }
+        let (k, ids) = g.scc_ids();
+        assert_eq!(k, n);
+        assert!((0..n).all(|v| ids[v] == n - 1 - v));
+        g.add_edge(0, n - 1, 1);
+        assert_eq!(g.scc_ids().0, 1);
+    }
+
+    #[test]
+    fn condensation() {
+        let mut g = Graph::new(6);
+        for &(from, to, weight) in &[(0, 1, 1), (1, 0, 2), (1, 2, 3), (2, 3, 4), (3, 2, 5), (0, 3, 6), (4, 5, 7)] {
+            g.add_edge(from, to, weight);
+        }
+        let c = g.condensation();
+        assert_eq!(c.groups.len(), 4);
+        for from in 0..6 {
+            for &(_, to, _) in &g.g[from] {
+                assert!(c.ids[from] <= c.ids[to]);
+            }
+        }
+        let (a, b) = (c.ids[0], c.ids[2]);
+        let mut weights = c.dag.g[a].iter().filter(|&&(_, to, _)| to == b).map(|&(_, _, w)| w).collect::<Vec<_>>();
+        weights.sort();
+        assert_eq!(weights, vec![3, 6]);
+        assert_eq!(c.dag.n, 4);
+        assert_eq!(c.dag.scc().len(), 4);
+
+        let sums = c.aggregate(&[1, 2, 4, 8, 16, 32], |x, y| x + y);
+        assert_eq!(sums[a], 3);
+        assert_eq!(sums[b], 12);
+        assert_eq!(sums[c.ids[4]], 16);
+    }
+
+    #[test]
+    fn tsort() {
+        for order in [TsortOrder::Smallest, TsortOrder::Largest, TsortOrder::Fifo] {
+            let order = sample().tsort(order).unwrap();
+            assert_eq!(order.len(), 5);
+            let pos = |v: usize| order.iter().position(|&x| x == v).unwrap();
+            for from in 0..5 {
+                for &(_, to, _) in &sample().g[from] {
+                    assert!(pos(from) < pos(to));
+                }
+            }
+        }
+        assert_eq!(sample().tsort(TsortOrder::Smallest), Ok(vec![0, 2, 1, 3, 4]));
+        assert_eq!(sample().tsort(TsortOrder::Largest), Ok(vec![4, 0, 2, 1, 3]));
+        assert_eq!(sample().tsort(TsortOrder::Fifo), Ok(vec![0, 4, 2, 1, 3]));
+    }
+
+    #[test]
+    fn tsort_cycle() {
+        let mut g = Graph::new(6);
+        for &(from, to) in &[(5, 0), (0, 1), (1, 2), (2, 3), (3, 1), (3, 4)] {
+            g.add_edge(from, to, 1);
+        }
+        let Err(Cycle(cycle)) = g.tsort(TsortOrder::Fifo) else { panic!() };
+        assert_eq!(cycle.len(), 3);
+        for i in 0..3 {
+            assert!(g.g[cycle[i]].iter().any(|&(_, to, _)| to == cycle[(i + 1) % 3]));
+        }
+        let mut g = Graph::new(1);
+        g.add_edge(0, 0, 1);
+        assert_eq!(g.tsort(TsortOrder::Smallest), Err(Cycle(vec![0])));
+    }
+
+    #[test]
+    fn count_tsort() {
+        assert_eq!(sample().count_tsort(), 5);
+        assert_eq!(Graph::<i64>::new(4).count_tsort(), 24);
+        let mut g = Graph::new(3);
+        g.add_edge(0, 1, 1);
+        g.add_edge(1, 0, 1);
+        assert_eq!(g.count_tsort(), 0);
+    }
+
+    #[test]
+    fn kruskal() {
+        let mut g = Graph::new(4);
+        for &(u, v, w) in &[(0, 1, 1), (1, 2, 2), (0, 2, 2), (2, 3, 5), (1, 3, 3)] {
+            g.add_edge(u, v, w);
+            g.add_edge(v, u, w);
+        }
+        let mst = g.kruskal();
+        assert_eq!(mst.len(), 3);
+        assert_eq!(mst.iter().map(|e| e.weight).sum::<i64>(), 6);
+    }
+
+    #[test]
+    fn mst_variants() {
+        use rand::{Rng, SeedableRng};
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(3);
+        for _ in 0..200 {
+            let n = rng.gen_range(1..6);
+            let mut g = Graph::new(n);
+            for _ in 0..rng.gen_range(0..9) {
+                g.add_undirected_edge(rng.gen_range(0..n), rng.gen_range(0..n), rng.gen_range(0..4_i64));
+            }
+            let m = g.edges.len();
+            let mst = g.mst();
+            let sorted = |mut edges: Vec<usize>| {
+                edges.sort();
+                edges
+            };
+            assert_eq!((g.prim().weight, sorted(g.prim().edges), g.prim().components), (mst.weight, sorted(mst.edges.clone()), mst.components));
+            assert_eq!((g.boruvka().weight, sorted(g.boruvka().edges), g.boruvka().components), (mst.weight, sorted(mst.edges.clone()), mst.components));
+
+            // Every spanning forest with the same components as (weight, edge mask).
+            let mut forests = vec![];
+            for mask in 0..1_usize << m {
+                let mut uf = ac_library::Dsu::new(n);
+                let mut weight = 0;
+                let acyclic = (0..m).filter(|&id| mask >> id & 1 == 1).all(|id| {
+                    weight += g.edges[id].weight;
+                    let (u, v) = (g.edges[id].from, g.edges[id].to);
+                    !uf.same(u, v) && { uf.merge(u, v); true }
+                });
+                if acyclic && mask.count_ones() as usize == n - mst.components {
+                    forests.push((weight, mask));
+                }
+            }
+            let min = forests.iter().map(|f| f.0).min().unwrap();
+            assert_eq!(mst.weight, min);
+            let mst_mask = mst.edges.iter().map(|&id| 1 << id).sum::<usize>();
+            let second = forests.iter().filter(|f| f.1 != mst_mask).map(|f| f.0).min();
+            let res = g.second_best_mst();
+            assert_eq!(res.as_ref().map(|r| r.weight), second);
+            if let Some(r) = res {
+                let mask = r.edges.iter().map(|&id| 1 << id).sum::<usize>();
+                assert!(forests.contains(&(r.weight, mask)) && mask != mst_mask);
+            }
+            let membership = g.mst_membership();
+            for id in 0..m {
+                let count = forests.iter().filter(|f| f.0 == min && f.1 >> id & 1 == 1).count();
+                let expected = match count {
+                    0 => MstMembership::Never,
+                    c if c == forests.iter().filter(|f| f.0 == min).count() => MstMembership::Always,
+                    _ => MstMembership::Sometimes,
+                };
+                assert_eq!(membership[id], expected);
+            }
+        }
+    }
+
+    #[test]
+    fn euler_trail() {
+        use rand::{Rng, SeedableRng};
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(4);
+        for _ in 0..300 {
+            let n = rng.gen_range(1..6);
+            let undirected = rng.gen();
+            let mut g = Graph::new(n);
+            for _ in 0..rng.gen_range(0..8) {
+                let (u, v) = (rng.gen_range(0..n), rng.gen_range(0..n));
+                if undirected { g.add_undirected_edge(u, v, 1) } else { g.add_edge(u, v, 1) };
+            }
+            let m = g.edges.len();
+            // Where a trail from `start` using every edge can end, by DP over subsets of edges and the current vertex.
+            let ends = |start: usize| {
+                let mut dp = vec![vec![false; n]; 1 << m];
+                dp[0][start] = true;
+                for mask in 0..1_usize << m {
+                    for v in 0..n {
+                        if !dp[mask][v] {
+                            continue;
+                        }
+                        for e in g.edges.iter().filter(|e| mask >> e.id & 1 == 0) {
+                            if e.from == v {
+                                dp[mask | 1 << e.id][e.to] = true;
+                            }
+                            if undirected && e.to == v {
+                                dp[mask | 1 << e.id][e.from] = true;
+                            }
+                        }
+                    }
+                }
+                dp[(1 << m) - 1].clone()
+            };
+            let exists = (0..n).any(|v| ends(v).contains(&true));
+            let closed = m > 0 && (0..n).any(|v| g.edges.iter().any(|e| e.from == v || e.to == v) && ends(v)[v]);
+            match g.euler_trail() {
+                Some(Trail {vertices, edges}) => {
+                    assert!(exists);
+                    let mut ids = edges.iter().map(|e| e.id).collect::<Vec<_>>();
+                    ids.sort();
+                    assert_eq!(ids, (0..m).collect::<Vec<_>>());
+                    for (i, e) in edges.iter().enumerate() {
+                        assert!(*e == g.edges[e.id] || (undirected && *e == g.edges[e.id].rev()));
+                        assert_eq!((vertices[i], vertices[i + 1]), (e.from, e.to));
+                    }
+                    // A circuit is returned whenever the trail can be closed.
+                    assert_eq!(m > 0 && vertices.first() == vertices.last(), closed);
+                    assert_eq!(g.euler_circuit().is_some(), closed || m == 0);
+                }
+                None => assert!(!exists),
+            }
+        }
+    }
+
+    #[test]
+    fn de_bruijn() {
+        for (k, n) in [(1, 1), (2, 1), (1, 3), (2, 3), (3, 2), (2, 5), (4, 3)] {
+            let seq = super::de_bruijn(k, n);
+            assert_eq!(seq.len(), k.pow(n as u32));
+            let mut words = (0..seq.len()).map(|i| (0..n).map(|j| seq[(i + j) % seq.len()]).collect::<Vec<_>>()).collect::<Vec<_>>();
+            words.sort();
+            words.dedup();
+            assert_eq!(words.len(), seq.len());
+            assert!(seq.iter().all(|&c| c < k));
+        }
+    }
+
+    #[test]
+    fn k_times_transition() {
+        let mut g = Graph::new(4);
+        for &(from, to) in &[(0, 1), (1, 2), (2, 0), (3, 1)] {
+            g.add_edge(from, to, 0);
+        }
+        assert_eq!(g.k_times_transition(0), vec![0, 1, 2, 3]);
+        assert_eq!(g.k_times_transition(1), vec![1, 2, 0, 1]);
+        assert_eq!(g.k_times_transition(5), vec![2, 0, 1, 2]);
+        assert_eq!(g.k_times_transition(1_000_000_000_000), vec![1, 2, 0, 1]);
+        assert_eq!(g.k_times_transition(u64::MAX), vec![0, 1, 2, 0]);
+    }
+}
@@ -0,0 +1,257 @@
+
+/* Position {{{ */
+/// Cell of a `Grid`, convertible to and from `(row, column)`.
+pub trait Position: Copy {
+    fn to_row_col(self) -> (usize, usize);
+    fn from_row_col(r: usize, c: usize) -> Self;
+}
+
+impl Position for (usize, usize) {
+    fn to_row_col(self) -> (usize, usize) {
+        return self;
+    }
+
+    fn from_row_col(r: usize, c: usize) -> Self {
+        return (r, c);
+    }
+}
+
+/// `x` is the row and `y` is the column.
+impl Position for super::coord::Coord<usize> {
+    fn to_row_col(self) -> (usize, usize) {
+        return (self.x, self.y);
+    }
+
+    fn from_row_col(r: usize, c: usize) -> Self {
+        return Self {x: r, y: c};
+    }
+}
+/* }}} */
+
+
+/// Up, right, down and left as `(row, column)` offsets.
+pub const DIR4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+/// `DIR4` and the diagonals, clockwise from up.
+pub const DIR8: [(isize, isize); 8] = [(-1, 0), (-1, 1), (0, 1), (1, 1), (1, 0), (1, -1), (0, -1), (-1, -1)];
+
+
+/* Grid {{{ */
+/// Rectangular grid of cells, some of which are walls, seen as a graph between adjacent cells.
+///
+/// Cells are numbered `r * w + c`, which is the vertex numbering of the `ShortestPath` returned by the searches.
+/// The searches take the moves as a slice of offsets such as `DIR4` or `DIR8`,
+/// and the `id` of each edge in the result is the index of the move in that slice.
+#[derive(Clone, Debug)]
+pub struct Grid<T = u8> {
+    pub h: usize,
+    pub w: usize,
+    pub cells: Vec<Vec<T>>,
+    wall: Vec<bool>,
+}
+
+impl<T> Grid<T> {
+    /* new {{{ */
+    /// Wraps `cells`, such as `[Bytes; h]` or `[Chars; h]` from `input!`, where the cells satisfying `is_wall` cannot be entered.
+    ///
+    /// Time complexity is O(HW)
+    pub fn new(cells: Vec<Vec<T>>, is_wall: impl Fn(&T) -> bool) -> Self {
+        let h = cells.len();
+        let w = cells.first().map_or(0, |row| row.len());
+        assert!(cells.iter().all(|row| row.len() == w), "rows have different lengths");
+        let wall = cells.iter().flatten().map(is_wall).collect();
+        return Self {h, w, cells, wall};
+    }
+    /* }}} */
+
+
+    pub fn index<P: Position>(&self, p: P) -> usize {
+        let (r, c) = p.to_row_col();
+        debug_assert!(r < self.h && c < self.w);
+        return r * self.w + c;
+    }
+
+    pub fn pos<P: Position>(&self, i: usize) -> P {
+        return P::from_row_col(i / self.w, i % self.w);
+    }
+
+    pub fn is_wall<P: Position>(&self, p: P) -> bool {
+        return self.wall[self.index(p)];
+    }
+
+    /// Returns the first cell in row-major order satisfying `pred`.
+    ///
+    /// Time complexity is O(HW)
+    pub fn find<P: Position>(&self, pred: impl Fn(&T) -> bool) -> Option<P> {
+        let i = self.cells.iter().flatten().position(pred)?;
+        return Some(self.pos(i));
+    }
+
+
+    /* neighbors {{{ */
+    /// Returns the cells reached from `p` by the moves in `dirs` that stay inside the grid and do not enter a wall.
+    pub fn neighbors<'a, P: Position + 'a>(&'a self, p: P, dirs: &'a [(isize, isize)]) -> impl Iterator<Item = P> + 'a {
+        let i = self.index(p);
+        return self.moves(i, dirs).map(move |(_, to)| self.pos(to));
+    }
+
+    pub fn neighbors4<'a, P: Position + 'a>(&'a self, p: P) -> impl Iterator<Item = P> + 'a {
+        return self.neighbors(p, &DIR4);
+    }
+
+    pub fn neighbors8<'a, P: Position + 'a>(&'a self, p: P) -> impl Iterator<Item = P> + 'a {
+        return self.neighbors(p, &DIR8);
+    }
+
+    /// Returns `(index in dirs, cell)` for each move from cell `i`, like `neighbors`.
+    fn moves<'a>(&'a self, i: usize, dirs: &'a [(isize, isize)]) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let (r, c) = (i / self.w, i % self.w);
+        return dirs.iter().enumerate().filter_map(move |(d, &(dr, dc))| {
+            let nr = r.checked_add_signed(dr).filter(|&nr| nr < self.h)?;
+            let nc = c.checked_add_signed(dc).filter(|&nc| nc < self.w)?;
+            let to = nr * self.w + nc;
+            return if self.wall[to] { None } else { Some((d, to)) };
+        });
+    }
+    /* }}} */
+
+
+    /* bfs {{{ */
+    /// Returns the number of moves from `start` to every cell, with the last move of a shortest path to each cell.
+    /// `start` itself may be a wall.
+    ///
+    /// Time complexity is O(HW |dirs|)
+    pub fn bfs<P: Position>(&self, start: P, dirs: &[(isize, isize)]) -> super::graph::ShortestPath<i64> {
+        return super::graph::bfs_by_out_edges(self.h * self.w, self.index(start), |cur, f| {
+            for (id, to) in self.moves(cur, dirs) {
+                f(id, to, 1);
+            }
+        });
+    }
+    /* }}} */
+
+
+    /* zero_one_bfs {{{ */
+    /// Same as `dijkstra`, for costs that are all 0 or 1.
+    ///
+    /// Time complexity is O(HW |dirs|)
+    pub fn zero_one_bfs<P: Position, W: super::graph::Weight>(&self, start: P, dirs: &[(isize, isize)], cost: impl Fn(P, P) -> W) -> super::graph::ShortestPath<W> {
+        return super::graph::zero_one_bfs_by_out_edges(self.h * self.w, self.index(start), |cur, f| {
+            for (id, to) in self.moves(cur, dirs) {
+                f(id, to, cost(self.pos(cur), self.pos(to)));
+            }
+        });
+    }
+    /* }}} */
+
+
+    /* dijkstra {{{ */
+    /// Returns the shortest paths from `start` where moving from `p` to an adjacent `q` costs `cost(p, q)`.
+    /// Costs must be non-negative.
+    ///
+    /// Time complexity is O(HW |dirs| log HW)
+    pub fn dijkstra<P: Position, W: super::graph::Weight>(&self, start: P, dirs: &[(isize, isize)], cost: impl Fn(P, P) -> W) -> super::graph::ShortestPath<W> {
+        return super::graph::dijkstra_by_out_edges(self.h * self.w, self.index(start), |cur, f| {
+            for (id, to) in self.moves(cur, dirs) {
+                f(id, to, cost(self.pos(cur), self.pos(to)));
+            }
+        });
+    }
+    /* }}} */
+
+
+    /// Returns the cells of a shortest path in `sp` from its start to `goal`, or `None` if `goal` is unreachable.
+    ///
+    /// Time complexity is O(path length)
+    pub fn path_to<P: Position, D: Copy, W: Copy>(&self, sp: &super::graph::ShortestPath<D, W>, goal: P) -> Option<Vec<P>> {
+        let (vertices, _) = sp.path_to(self.index(goal))?;
+        return Some(vertices.into_iter().map(|i| self.pos(i)).collect());
+    }
+}
+
+impl<T, P: Position> std::ops::Index<P> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: P) -> &T {
+        let (r, c) = p.to_row_col();
+        return &self.cells[r][c];
+    }
+}
+
+impl<T, P: Position> std::ops::IndexMut<P> for Grid<T> {
+    /// Walls stay as given to `new`.
+    fn index_mut(&mut self, p: P) -> &mut T {
+        let (r, c) = p.to_row_col();
+        return &mut self.cells[r][c];
+    }
+}
+/* }}} */
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coord::Coord;
+    use crate::graph::{Adjacency, Graph};
+    use rand::{Rng, SeedableRng};
+
+    fn maze() -> Grid<char> {
+        let s = ["S.#.", "..#.", "#...", "..#G"];
+        return Grid::new(s.iter().map(|row| row.chars().collect()).collect(), |&ch| ch == '#');
+    }
+
+    #[test]
+    fn positions() {
+        let g = maze();
+        assert_eq!((g.h, g.w), (4, 4));
+        assert_eq!(g.index((2, 1)), 9);
+        assert_eq!(g.pos::<(usize, usize)>(9), (2, 1));
+        assert_eq!(g.pos::<Coord<usize>>(7), Coord {x: 1, y: 3});
+        assert_eq!(g.find(|&ch| ch == 'G'), Some((3, 3)));
+        assert_eq!(g[Coord {x: 0, y: 2}], '#');
+        assert!(g.is_wall((3, 2)));
+        assert_eq!(g.neighbors4((0, 0)).collect::<Vec<_>>(), vec![(0, 1), (1, 0)]);
+        assert_eq!(g.neighbors4((1, 1)).collect::<Vec<_>>(), vec![(0, 1), (2, 1), (1, 0)]);
+        assert_eq!(g.neighbors8((1, 1)).collect::<Vec<_>>(), vec![(0, 1), (2, 2), (2, 1), (1, 0), (0, 0)]);
+        assert_eq!(g.neighbors8(Coord {x: 3, y: 3}).count(), 2);
+    }
+
+    #[test]
+    fn bfs() {
+        let g = maze();
+        let sp = g.bfs((0, 0), &DIR4);
+        assert_eq!(sp.dist[g.index((3, 3))], Some(6));
+        assert_eq!(sp.dist[g.index((0, 3))], Some(7));
+        assert_eq!(sp.dist[g.index((0, 2))], None);
+        let path = g.path_to(&sp, Coord {x: 3, y: 3}).unwrap();
+        assert_eq!(path.len(), 7);
+        assert!(path.windows(2).all(|p| p[0].x.abs_diff(p[1].x) + p[0].y.abs_diff(p[1].y) == 1));
+        let sp8 = g.bfs((0, 0), &DIR8);
+        assert_eq!(sp8.dist[g.index((3, 3))], Some(3));
+        assert_eq!(sp8.prev[g.index((1, 1))].unwrap().id, 3);
+    }
+
+    #[test]
+    fn against_graph() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+        for _ in 0..30 {
+            let (h, w) = (rng.gen_range(1..8), rng.gen_range(1..8));
+            let cells = (0..h).map(|_| (0..w).map(|_| rng.gen_range(0..4u8)).collect()).collect();
+            let grid = Grid::new(cells, |&x| x == 0);
+            let start = (rng.gen_range(0..h), rng.gen_range(0..w));
+            for dirs in [&DIR4[..], &DIR8[..]] {
+                let cost = |_: (usize, usize), q: (usize, usize)| grid[q] as i64 - 1;
+                let mut graph = Graph::new(h * w);
+                for i in 0..h * w {
+                    for q in grid.neighbors(grid.pos(i), dirs) {
+                        graph.add_edge(i, grid.index(q), cost(grid.pos(i), q));
+                    }
+                }
+                let s = grid.index(start);
+                assert_eq!(grid.dijkstra(start, dirs, cost).dist, graph.dijkstra(s));
+                assert_eq!(grid.bfs(start, dirs).dist, graph.bfs(s));
+                let binary = |p, q| cost(p, q).min(1);
+                assert_eq!(grid.zero_one_bfs(start, dirs, binary).dist, grid.dijkstra(start, dirs, binary).dist);
+            }
+        }
+    }
+}
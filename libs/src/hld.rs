@@ -0,0 +1,342 @@
+
+/* Hld {{{ */
+/// Heavy-light decomposition of a rooted tree.
+///
+/// Vertices are laid out so that every heavy path and every subtree is a contiguous range of positions,
+/// and any path is covered by O(log V) ranges.
+#[derive(Clone, Debug)]
+pub struct Hld {
+    pub n: usize,
+    /// Position of each vertex in the layout.
+    pub pos: Vec<usize>,
+    /// Vertex at each position.
+    pub vertex: Vec<usize>,
+    /// Topmost vertex of the heavy path containing each vertex.
+    pub head: Vec<usize>,
+    parent: Vec<Option<usize>>,
+    size: Vec<usize>,
+}
+
+impl Hld {
+    /* new {{{ */
+    /// Decomposes `tree`, laying out the heavy child of each vertex right after it.
+    ///
+    /// Time complexity is O(V)
+    pub fn new(tree: &super::tree::Tree) -> Self {
+        let n = tree.n;
+        let mut pos = vec![0; n];
+        let mut vertex = Vec::with_capacity(n);
+        let mut head = vec![tree.root; n];
+        let mut stack = vec![tree.root];
+        while let Some(v) = stack.pop() {
+            pos[v] = vertex.len();
+            vertex.push(v);
+            let heavy = tree.children[v].iter().copied().max_by_key(|&c| tree.size[c]);
+            // The heavy child is pushed last so that it is visited next.
+            for &c in &tree.children[v] {
+                if Some(c) != heavy {
+                    head[c] = c;
+                    stack.push(c);
+                }
+            }
+            if let Some(c) = heavy {
+                head[c] = head[v];
+                stack.push(c);
+            }
+        }
+        return Self {n, pos, vertex, head, parent: tree.parent.clone(), size: tree.size.clone()};
+    }
+    /* }}} */
+
+
+    /* lca {{{ */
+    /// Returns the lowest common ancestor of `u` and `v`.
+    ///
+    /// Time complexity is O(log V)
+    pub fn lca(&self, mut u: usize, mut v: usize) -> usize {
+        while self.head[u] != self.head[v] {
+            if self.pos[self.head[u]] > self.pos[self.head[v]] {
+                u = self.parent[self.head[u]].unwrap();
+            } else {
+                v = self.parent[self.head[v]].unwrap();
+            }
+        }
+        return if self.pos[u] < self.pos[v] { u } else { v };
+    }
+    /* }}} */
+
+
+    /* path_ranges {{{ */
+    /// Returns ranges of positions covering the path from `u` to `v`, in the order the path visits them.
+    /// A range flagged `true` is visited from its last position to its first, i.e. upwards.
+    /// If `edge` is true the lowest common ancestor is left out, for values stored on the edge to the parent.
+    ///
+    /// Time complexity is O(log V)
+    pub fn path_ranges(&self, mut u: usize, mut v: usize, edge: bool) -> Vec<(std::ops::Range<usize>, bool)> {
+        let mut up = vec![];
+        let mut down = vec![];
+        while self.head[u] != self.head[v] {
+            if self.pos[self.head[u]] > self.pos[self.head[v]] {
+                up.push((self.pos[self.head[u]]..self.pos[u] + 1, true));
+                u = self.parent[self.head[u]].unwrap();
+            } else {
+                down.push((self.pos[self.head[v]]..self.pos[v] + 1, false));
+                v = self.parent[self.head[v]].unwrap();
+            }
+        }
+        if self.pos[u] >= self.pos[v] {
+            up.push((self.pos[v] + edge as usize..self.pos[u] + 1, true));
+        } else {
+            down.push((self.pos[u] + edge as usize..self.pos[v] + 1, false));
+        }
+        up.extend(down.into_iter().rev());
+        up.retain(|(range, _)| !range.is_empty());
+        return up;
+    }
+    /* }}} */
+
+
+    /* subtree_range {{{ */
+    /// Returns the range of positions of the subtree of `v`.
+    ///
+    /// Time complexity is O(1)
+    pub fn subtree_range(&self, v: usize) -> std::ops::Range<usize> {
+        return self.pos[v]..self.pos[v] + self.size[v];
+    }
+    /* }}} */
+
+
+    /* layout {{{ */
+    /// Returns `values` indexed by vertex rearranged to be indexed by position.
+    ///
+    /// Time complexity is O(V)
+    pub fn layout<T: Clone>(&self, values: &[T]) -> Vec<T> {
+        return self.vertex.iter().map(|&v| values[v].clone()).collect();
+    }
+    /* }}} */
+}
+/* }}} */
+
+
+/* HldSegtree {{{ */
+/// Vertex values on a tree with point updates and products along directed paths.
+/// Keeps a second segment tree with the reversed monoid, so `M` need not be commutative.
+pub struct HldSegtree<M: ac_library::Monoid> {
+    pub hld: Hld,
+    seg: ac_library::Segtree<M>,
+    rev: ac_library::Segtree<super::monoid::Reversed<M>>,
+}
+
+impl<M: ac_library::Monoid> HldSegtree<M> {
+    /// Time complexity is O(V)
+    pub fn new(hld: Hld, values: &[M::S]) -> Self {
+        let values = hld.layout(values);
+        return Self {seg: values.clone().into(), rev: values.into(), hld};
+    }
+
+    /// Time complexity is O(log V)
+    pub fn set(&mut self, v: usize, x: M::S) {
+        self.seg.set(self.hld.pos[v], x.clone());
+        self.rev.set(self.hld.pos[v], x);
+    }
+
+    /// Time complexity is O(1)
+    pub fn get(&self, v: usize) -> M::S {
+        return self.seg.get(self.hld.pos[v]);
+    }
+
+    /// Returns the product of the values on the path from `u` to `v` in the order it visits them.
+    ///
+    /// Time complexity is O(log^2 V)
+    pub fn path_prod(&self, u: usize, v: usize) -> M::S {
+        let mut acc = M::identity();
+        for (range, up) in self.hld.path_ranges(u, v, false) {
+            let x = if up { self.rev.prod(range) } else { self.seg.prod(range) };
+            acc = M::binary_operation(&acc, &x);
+        }
+        return acc;
+    }
+
+    /// Returns the product of the values in the subtree of `v`, in layout order.
+    ///
+    /// Time complexity is O(log V)
+    pub fn subtree_prod(&self, v: usize) -> M::S {
+        return self.seg.prod(self.hld.subtree_range(v));
+    }
+}
+/* }}} */
+
+
+/* HldLazySegtree {{{ */
+/// Vertex values on a tree with updates and products along paths and subtrees.
+/// Keeps a second segment tree with the reversed monoid, so `F::M` need not be commutative.
+pub struct HldLazySegtree<F: ac_library::MapMonoid> {
+    pub hld: Hld,
+    seg: ac_library::LazySegtree<F>,
+    rev: ac_library::LazySegtree<super::map_monoid::Reversed<F>>,
+}
+
+impl<F: ac_library::MapMonoid> HldLazySegtree<F> {
+    /// Time complexity is O(V)
+    pub fn new(hld: Hld, values: &[<F::M as ac_library::Monoid>::S]) -> Self {
+        let values = hld.layout(values);
+        return Self {seg: values.clone().into(), rev: values.into(), hld};
+    }
+
+    /// Time complexity is O(log V)
+    pub fn set(&mut self, v: usize, x: <F::M as ac_library::Monoid>::S) {
+        self.seg.set(self.hld.pos[v], x.clone());
+        self.rev.set(self.hld.pos[v], x);
+    }
+
+    /// Time complexity is O(log V)
+    pub fn get(&mut self, v: usize) -> <F::M as ac_library::Monoid>::S {
+        return self.seg.get(self.hld.pos[v]);
+    }
+
+    /// Returns the product of the values on the path from `u` to `v` in the order it visits them.
+    ///
+    /// Time complexity is O(log^2 V)
+    pub fn path_prod(&mut self, u: usize, v: usize) -> <F::M as ac_library::Monoid>::S {
+        let mut acc = F::identity_element();
+        for (range, up) in self.hld.path_ranges(u, v, false) {
+            let x = if up { self.rev.prod(range) } else { self.seg.prod(range) };
+            acc = F::binary_operation(&acc, &x);
+        }
+        return acc;
+    }
+
+    /// Applies `f` to every value on the path between `u` and `v`.
+    ///
+    /// Time complexity is O(log^2 V)
+    pub fn path_apply(&mut self, u: usize, v: usize, f: F::F) {
+        for (range, _) in self.hld.path_ranges(u, v, false) {
+            self.seg.apply_range(range.clone(), f.clone());
+            self.rev.apply_range(range, f.clone());
+        }
+    }
+
+    /// Returns the product of the values in the subtree of `v`, in layout order.
+    ///
+    /// Time complexity is O(log V)
+    pub fn subtree_prod(&mut self, v: usize) -> <F::M as ac_library::Monoid>::S {
+        return self.seg.prod(self.hld.subtree_range(v));
+    }
+
+    /// Applies `f` to every value in the subtree of `v`.
+    ///
+    /// Time complexity is O(log V)
+    pub fn subtree_apply(&mut self, v: usize, f: F::F) {
+        let range = self.hld.subtree_range(v);
+        self.seg.apply_range(range.clone(), f.clone());
+        self.rev.apply_range(range, f);
+    }
+}
+/* }}} */
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map_monoid::Add;
+    use crate::monoid::Affine;
+    use crate::tree::Tree;
+    use ac_library::{Max, Monoid};
+    use rand::{Rng, SeedableRng};
+
+    fn random_tree(rng: &mut rand::rngs::SmallRng, n: usize) -> Tree {
+        let parent = (0..n).map(|v| if v == 0 { None } else { Some(rng.gen_range(0..v)) }).collect::<Vec<_>>();
+        return Tree::from_parents(&parent).reroot(rng.gen_range(0..n));
+    }
+
+    fn naive_path(t: &Tree, u: usize, v: usize) -> Vec<usize> {
+        let w = t.lca(u, v);
+        let mut up = vec![];
+        let mut x = u;
+        while x != w {
+            up.push(x);
+            x = t.parent[x].unwrap();
+        }
+        up.push(w);
+        let mut down = vec![];
+        let mut x = v;
+        while x != w {
+            down.push(x);
+            x = t.parent[x].unwrap();
+        }
+        up.extend(down.into_iter().rev());
+        return up;
+    }
+
+    #[test]
+    fn ranges() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+        for _ in 0..30 {
+            let n = rng.gen_range(1..30);
+            let t = random_tree(&mut rng, n);
+            let hld = Hld::new(&t);
+            for u in 0..n {
+                assert_eq!(hld.subtree_range(u).map(|p| hld.vertex[p]).filter(|&x| t.lca(x, u) == u).count(), t.size[u]);
+                for v in 0..n {
+                    assert_eq!(hld.lca(u, v), t.lca(u, v));
+                    let path = naive_path(&t, u, v);
+                    let walk = |edge| {
+                        hld.path_ranges(u, v, edge).into_iter().flat_map(|(range, up)| {
+                            let mut vs = range.map(|p| hld.vertex[p]).collect::<Vec<_>>();
+                            if up {
+                                vs.reverse();
+                            }
+                            vs
+                        }).collect::<Vec<_>>()
+                    };
+                    assert_eq!(walk(false), path);
+                    assert_eq!(walk(true), path.iter().copied().filter(|&x| x != t.lca(u, v)).collect::<Vec<_>>());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn non_commutative_path_prod() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(1);
+        for _ in 0..20 {
+            let n = rng.gen_range(1..25);
+            let t = random_tree(&mut rng, n);
+            let mut values = (0..n).map(|_| (rng.gen_range(-1..2), rng.gen_range(-5..6))).collect::<Vec<(i64, i64)>>();
+            let mut seg = HldSegtree::<Affine<i64>>::new(Hld::new(&t), &values);
+            for _ in 0..50 {
+                let v = rng.gen_range(0..n);
+                values[v] = (rng.gen_range(-1..2), rng.gen_range(-5..6));
+                seg.set(v, values[v]);
+                let (a, b) = (rng.gen_range(0..n), rng.gen_range(0..n));
+                let expected = naive_path(&t, a, b).iter().fold(Affine::<i64>::identity(), |acc, &x| {
+                    Affine::<i64>::binary_operation(&acc, &values[x])
+                });
+                assert_eq!(seg.path_prod(a, b), expected);
+                assert_eq!(seg.get(v), values[v]);
+            }
+        }
+    }
+
+    #[test]
+    fn lazy_path_and_subtree() {
+        //     0
+        //    / \
+        //   1   2
+        //  / \
+        // 3   4
+        let t = Tree::from_parents(&[None, Some(0), Some(0), Some(1), Some(1)]);
+        let mut seg = HldLazySegtree::<Add<Max<i64>>>::new(Hld::new(&t), &[1, 2, 3, 4, 5]);
+        assert_eq!(seg.path_prod(3, 2), 4);
+        seg.path_apply(3, 2, 10);
+        assert_eq!(seg.path_prod(4, 4), 5);
+        assert_eq!(seg.path_prod(4, 1), 12);
+        assert_eq!(seg.subtree_prod(1), 14);
+        seg.subtree_apply(1, -20);
+        assert_eq!(seg.path_prod(3, 4), -6);
+        assert_eq!(seg.subtree_prod(0), 13);
+        seg.set(4, 100);
+        assert_eq!(seg.get(4), 100);
+        assert_eq!(seg.path_prod(2, 3), 13);
+    }
+}
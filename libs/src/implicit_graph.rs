@@ -0,0 +1,256 @@
+
+/* ImplicitSearch {{{ */
+/// Result of `bfs_by` or `dijkstra_by` over states generated on the fly.
+///
+/// States are numbered in the order they are reached, the start state being 0.
+/// The map from states to numbers uses the hasher `H`, such as `rustc_hash::FxBuildHasher`.
+#[derive(Clone, Debug)]
+pub struct ImplicitSearch<S, W = i64, H = std::collections::hash_map::RandomState> {
+    /// Every state reached, in the order it was reached.
+    pub states: Vec<S>,
+    pub ids: std::collections::HashMap<S, usize, H>,
+    dist: Vec<W>,
+    /// Whether `dist` is final, which is not the case for states left in the queue when the search stops at a goal.
+    settled: Vec<bool>,
+    prev: Vec<Option<usize>>,
+    goal: Option<usize>,
+}
+
+impl<S: Clone + Eq + std::hash::Hash, W: Copy, H: std::hash::BuildHasher + Default> ImplicitSearch<S, W, H> {
+    fn new(start: S, zero: W) -> Self {
+        let mut res = Self {states: vec![], ids: std::collections::HashMap::default(), dist: vec![], settled: vec![], prev: vec![], goal: None};
+        res.reach(start, zero, None);
+        return res;
+    }
+
+    /// Records `state` with a tentative distance and returns its number.
+    fn reach(&mut self, state: S, d: W, prev: Option<usize>) -> usize {
+        let id = self.states.len();
+        self.ids.insert(state.clone(), id);
+        self.states.push(state);
+        self.dist.push(d);
+        self.settled.push(false);
+        self.prev.push(prev);
+        return id;
+    }
+
+    /// Returns the distance from the start state to `state`, or `None` if it was not reached before the search stopped.
+    pub fn dist(&self, state: &S) -> Option<W> {
+        let &id = self.ids.get(state)?;
+        return if self.settled[id] { Some(self.dist[id]) } else { None };
+    }
+
+    /// Returns the states on a shortest path from the start state to `state`, or `None` if its distance is unknown.
+    ///
+    /// Time complexity is O(path length)
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        self.dist(state)?;
+        let mut path = vec![];
+        let mut cur = Some(self.ids[state]);
+        while let Some(id) = cur {
+            path.push(self.states[id].clone());
+            cur = self.prev[id];
+        }
+        path.reverse();
+        return Some(path);
+    }
+
+    /// Returns the goal state found, if any, with its distance.
+    pub fn goal(&self) -> Option<(&S, W)> {
+        let id = self.goal?;
+        return Some((&self.states[id], self.dist[id]));
+    }
+
+    /// Returns the states on a shortest path from the start state to the goal state found, if any.
+    ///
+    /// Time complexity is O(path length)
+    pub fn goal_path(&self) -> Option<Vec<S>> {
+        let (goal, _) = self.goal()?;
+        return self.path_to(goal);
+    }
+}
+/* }}} */
+
+
+/* bfs_by {{{ */
+/// Searches the states reachable from `start` with unit costs, where `neighbors(s)` lists the states reached from `s` in one move.
+/// Stops at the first state satisfying `is_goal`, which is closest to `start`; pass `|_| false` to visit every reachable state.
+///
+/// Time complexity is O(V + E) hash map operations
+pub fn bfs_by<S, I>(start: S, neighbors: impl FnMut(&S) -> I, is_goal: impl FnMut(&S) -> bool) -> ImplicitSearch<S>
+where
+    S: Clone + Eq + std::hash::Hash,
+    I: IntoIterator<Item = S>,
+{
+    return bfs_by_with_hasher(start, neighbors, is_goal);
+}
+
+/// Same as `bfs_by`, with the hasher `H` for the map of states.
+///
+/// Time complexity is O(V + E) hash map operations
+pub fn bfs_by_with_hasher<H, S, I>(start: S, mut neighbors: impl FnMut(&S) -> I, mut is_goal: impl FnMut(&S) -> bool) -> ImplicitSearch<S, i64, H>
+where
+    H: std::hash::BuildHasher + Default,
+    S: Clone + Eq + std::hash::Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut res = ImplicitSearch::new(start, 0);
+    res.settled[0] = true;
+    let mut i = 0;
+    while i < res.states.len() {
+        if is_goal(&res.states[i]) {
+            res.goal = Some(i);
+            break;
+        }
+        let d = res.dist[i];
+        for to in neighbors(&res.states[i]) {
+            if !res.ids.contains_key(&to) {
+                let id = res.reach(to, d + 1, Some(i));
+                res.settled[id] = true;
+            }
+        }
+        i += 1;
+    }
+    return res;
+}
+/* }}} */
+
+
+/* dijkstra_by {{{ */
+/// Searches the states reachable from `start`, where `neighbors(s)` lists `(state, cost)` for the moves from `s`.
+/// Costs must be non-negative.
+/// Stops at the first state satisfying `is_goal`, which is closest to `start`; pass `|_| false` to visit every reachable state.
+///
+/// Time complexity is O((V + E) log V) with O(V + E) hash map operations
+pub fn dijkstra_by<S, W, I>(start: S, neighbors: impl FnMut(&S) -> I, is_goal: impl FnMut(&S) -> bool) -> ImplicitSearch<S, W>
+where
+    S: Clone + Eq + std::hash::Hash,
+    W: super::graph::Weight,
+    I: IntoIterator<Item = (S, W)>,
+{
+    return dijkstra_by_with_hasher(start, neighbors, is_goal);
+}
+
+/// Same as `dijkstra_by`, with the hasher `H` for the map of states.
+///
+/// Time complexity is O((V + E) log V) with O(V + E) hash map operations
+pub fn dijkstra_by_with_hasher<H, S, W, I>(start: S, mut neighbors: impl FnMut(&S) -> I, mut is_goal: impl FnMut(&S) -> bool) -> ImplicitSearch<S, W, H>
+where
+    H: std::hash::BuildHasher + Default,
+    S: Clone + Eq + std::hash::Hash,
+    W: super::graph::Weight,
+    I: IntoIterator<Item = (S, W)>,
+{
+    use super::graph::MinByWeight;
+    let mut res = ImplicitSearch::new(start, W::zero());
+    let mut que = std::collections::BinaryHeap::from([MinByWeight(W::zero(), 0)]);
+    while let Some(MinByWeight(d, cur)) = que.pop() {
+        if std::mem::replace(&mut res.settled[cur], true) {
+            continue;
+        }
+        if is_goal(&res.states[cur]) {
+            res.goal = Some(cur);
+            break;
+        }
+        for (to, cost) in neighbors(&res.states[cur]) {
+            let nd = d.plus(cost);
+            match res.ids.get(&to) {
+                Some(&id) => {
+                    if !res.settled[id] && res.dist[id].compare(&nd).is_gt() {
+                        res.dist[id] = nd;
+                        res.prev[id] = Some(cur);
+                        que.push(MinByWeight(nd, id));
+                    }
+                }
+                None => {
+                    let id = res.reach(to, nd, Some(cur));
+                    que.push(MinByWeight(nd, id));
+                }
+            }
+        }
+    }
+    return res;
+}
+/* }}} */
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Adjacency, Graph};
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn bfs() {
+        // Reach 1 from `start` by doubling or subtracting 1.
+        let res = bfs_by(11_i64, |&x| [x * 2, x - 1].into_iter().filter(|y| (1..100).contains(y)), |&x| x == 1);
+        assert_eq!(res.goal(), Some((&1, 10)));
+        let path = res.goal_path().unwrap();
+        assert_eq!(path.len(), 11);
+        assert!(path.windows(2).all(|p| p[1] == p[0] * 2 || p[1] == p[0] - 1));
+        assert_eq!(res.dist(&11), Some(0));
+        assert_eq!(res.path_to(&11), Some(vec![11]));
+        assert_eq!(res.dist(&1000), None);
+
+        let all = bfs_by(5_i64, |&x| [x * 2, x - 1].into_iter().filter(|y| (1..100).contains(y)), |_| false);
+        assert_eq!(all.goal(), None);
+        // 99 could only come from 100, which is out of range.
+        assert_eq!(all.states.len(), 98);
+        assert_eq!(all.dist(&99), None);
+        assert_eq!(all.dist(&64), Some(5));
+    }
+
+    #[test]
+    fn fuel() {
+        // Walk right along a line with fuel stations, holding at most 3 units of fuel.
+        // Moving uses 1 unit and costs nothing, refuelling to full costs 1.
+        let station = [true, false, false, true, false, true, false, false, false, true];
+        let neighbors = |&(p, f): &(usize, u32)| {
+            let mut next = vec![];
+            if station[p] && f < 3 {
+                next.push(((p, 3), 1_i64));
+            }
+            if f > 0 && p + 1 < station.len() {
+                next.push(((p + 1, f - 1), 0));
+            }
+            next
+        };
+        let res = dijkstra_by((0, 3), neighbors, |&(p, _)| p == 8);
+        assert_eq!(res.goal(), Some((&(8, 0), 2)));
+        let path = res.goal_path().unwrap();
+        assert_eq!(path.first(), Some(&(0, 3)));
+        assert!(path.contains(&(3, 3)) && path.contains(&(5, 3)));
+        // The gap from 5 to 9 needs 4 units.
+        let res = dijkstra_by_with_hasher::<rustc_hash::FxBuildHasher, _, _, _>((0, 3), neighbors, |&(p, _)| p == 9);
+        assert_eq!(res.goal(), None);
+        assert_eq!(res.dist(&(8, 0)), Some(2));
+    }
+
+    #[test]
+    fn against_graph() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+        for _ in 0..50 {
+            let n = rng.gen_range(1..20);
+            let mut g = Graph::new(n);
+            for _ in 0..rng.gen_range(0..3 * n) {
+                g.add_edge(rng.gen_range(0..n), rng.gen_range(0..n), rng.gen_range(0..10_i64));
+            }
+            let neighbors = |&v: &usize| g.g[v].iter().map(|&(_, to, w)| (to, w)).collect::<Vec<_>>();
+            let dist = g.dijkstra(0);
+            let all = dijkstra_by(0, neighbors, |_| false);
+            let bfs = bfs_by(0, |&v| g.g[v].iter().map(|&(_, to, _)| to).collect::<Vec<_>>(), |_| false);
+            for v in 0..n {
+                assert_eq!(all.dist(&v), dist[v]);
+                assert_eq!(bfs.dist(&v), g.bfs(0)[v]);
+                if let Some(path) = all.path_to(&v) {
+                    let len = path.windows(2).map(|p| g.g[p[0]].iter().filter(|e| e.1 == p[1]).map(|e| e.2).min().unwrap()).sum::<i64>();
+                    assert_eq!(Some(len), dist[v]);
+                }
+            }
+            let target = rng.gen_range(0..n);
+            let res = dijkstra_by(0, neighbors, |&v| v == target);
+            assert_eq!(res.goal().map(|(_, d)| d), dist[target]);
+            assert_eq!(res.goal_path().map(|p| *p.last().unwrap()), dist[target].map(|_| target));
+        }
+    }
+}
@@ -0,0 +1,38 @@
+//! Library shared by the `algo` and `heuri` contest templates.
+//!
+//! Solutions `use libs::...` while developing locally, and `bundler` inlines
+//! the modules they use into a single file for submission.
+//! Modules refer to each other through `super::...` so that they work both here and when inlined.
+#![allow(clippy::needless_return, clippy::needless_range_loop, clippy::new_without_default)]
+// The judge runs Rust 1.70, so suggestions such as `Option::is_none_or` cannot be followed.
+#![allow(clippy::unnecessary_map_or)]
+
+pub mod ahc;
+#[cfg(feature = "break_point")]
+pub mod break_point;
+pub mod centroid;
+pub mod coord;
+pub mod flow;
+pub mod functional_graph;
+pub mod graph;
+pub mod grid;
+pub mod hld;
+pub mod implicit_graph;
+pub mod linear_programming;
+pub mod lowlink;
+pub mod map_monoid;
+pub mod matching;
+pub mod min_cost_flow;
+pub mod monoid;
+pub mod monoid_dsu;
+pub mod more_itertools;
+pub mod prime;
+pub mod raq;
+pub mod rerooting;
+pub mod ring;
+pub mod rolling_hash;
+pub mod skip_list;
+pub mod timer;
+pub mod tree;
+pub mod weighted_dsu;
+pub mod zobrist;
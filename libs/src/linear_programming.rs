@@ -1,8 +1,9 @@
 
 /* linear_programming {{{ */
 /// ax + by >= c を満たす (x, y) で、px + qy が最小となるものを計算する
-/// O(log(a + b))
-fn linear_programming(a: i64, b: i64, c: i64, p: i64, q: i64) -> (i64, i64) {
+/// a, b > 0 かつ x, y >= 0 とする
+/// O(a + b)
+pub fn linear_programming(a: i64, b: i64, c: i64, p: i64, q: i64) -> (i64, i64) {
     let mut min_cost = i64::MAX;
     let (mut x, mut y) = (0, 0);
 
@@ -26,3 +27,23 @@ fn linear_programming(a: i64, b: i64, c: i64, p: i64, q: i64) -> (i64, i64) {
     (x, y)
 }
 /* }}} */
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_brute_force() {
+        for (a, b, c, p, q) in itertools::iproduct!(1..6, 1..6, 0..20, 1..5, 1..5) {
+            let (x, y) = linear_programming(a, b, c, p, q);
+            assert!(x >= 0 && y >= 0 && a * x + b * y >= c);
+            let best = itertools::iproduct!(0..=20, 0..=20)
+                .filter(|&(x, y)| a * x + b * y >= c)
+                .map(|(x, y)| p * x + q * y)
+                .min()
+                .unwrap();
+            assert_eq!(p * x + q * y, best);
+        }
+    }
+}
@@ -0,0 +1,336 @@
+
+/* Lowlink {{{ */
+/// Lowlink of an undirected graph given as an edge list, where the index of an edge is its ID.
+/// Parallel edges and self-loops are allowed.
+#[derive(Clone, Debug)]
+pub struct Lowlink {
+    pub n: usize,
+    pub edges: Vec<(usize, usize)>,
+    /// Preorder index of each vertex in the DFS forest.
+    pub ord: Vec<usize>,
+    /// Smallest `ord` reachable from the subtree of each vertex using at most one non-tree edge.
+    pub low: Vec<usize>,
+    /// IDs of the bridges in increasing order.
+    pub bridges: Vec<usize>,
+    /// Articulation points in increasing order.
+    pub articulation_points: Vec<usize>,
+    /// Edge IDs of each biconnected component.
+    blocks: Vec<Vec<usize>>,
+    /// `(to, edge ID)` for each vertex.
+    adj: Vec<Vec<(usize, usize)>>,
+}
+
+impl Lowlink {
+    /* new {{{ */
+    /// Computes the lowlink with an iterative DFS, skipping only the edge ID to the parent
+    /// so that a parallel edge to the parent counts as a back edge.
+    ///
+    /// Time complexity is O(V + E)
+    pub fn new(n: usize, edges: &[(usize, usize)]) -> Self {
+        let mut adj = vec![vec![]; n];
+        for (id, &(u, v)) in edges.iter().enumerate() {
+            adj[u].push((v, id));
+            if u != v {
+                adj[v].push((u, id));
+            }
+        }
+        let mut ord = vec![usize::MAX; n];
+        let mut low = vec![0; n];
+        let mut is_bridge = vec![false; edges.len()];
+        let mut is_articulation = vec![false; n];
+        let mut blocks = vec![];
+        let mut edge_stack = vec![];
+        let mut now = 0;
+        for root in 0..n {
+            if ord[root] != usize::MAX {
+                continue;
+            }
+            ord[root] = now;
+            low[root] = now;
+            now += 1;
+            let mut root_children = 0;
+            // (vertex, edge ID from the parent, next index in adj)
+            let mut call = vec![(root, usize::MAX, 0)];
+            while let Some(top) = call.last_mut() {
+                let (v, parent_edge, i) = *top;
+                if i < adj[v].len() {
+                    top.2 += 1;
+                    let (to, id) = adj[v][i];
+                    if id == parent_edge || to == v {
+                        continue;
+                    }
+                    if ord[to] == usize::MAX {
+                        ord[to] = now;
+                        low[to] = now;
+                        now += 1;
+                        edge_stack.push(id);
+                        call.push((to, id, 0));
+                    } else if ord[to] < ord[v] {
+                        low[v] = low[v].min(ord[to]);
+                        edge_stack.push(id);
+                    }
+                    continue;
+                }
+                call.pop();
+                let Some(&(p, _, _)) = call.last() else { continue };
+                low[p] = low[p].min(low[v]);
+                if low[v] > ord[p] {
+                    is_bridge[parent_edge] = true;
+                }
+                if low[v] >= ord[p] {
+                    if p == root {
+                        root_children += 1;
+                    } else {
+                        is_articulation[p] = true;
+                    }
+                    let mut block = vec![];
+                    while let Some(id) = edge_stack.pop() {
+                        block.push(id);
+                        if id == parent_edge {
+                            break;
+                        }
+                    }
+                    blocks.push(block);
+                }
+            }
+            if root_children >= 2 {
+                is_articulation[root] = true;
+            }
+        }
+        let bridges = (0..edges.len()).filter(|&id| is_bridge[id]).collect();
+        let articulation_points = (0..n).filter(|&v| is_articulation[v]).collect();
+        return Self {n, edges: edges.to_vec(), ord, low, bridges, articulation_points, blocks, adj};
+    }
+    /* }}} */
+
+
+    /* from_graph {{{ */
+    /// Same as `new` with the edges of `g` ignoring their direction, so edge IDs are those of `g`.
+    ///
+    /// Time complexity is O(V + E)
+    pub fn from_graph<W>(g: &super::graph::Graph<W>) -> Self {
+        return Self::new(g.n, &g.edges.iter().map(|e| (e.from, e.to)).collect::<Vec<_>>());
+    }
+    /* }}} */
+
+
+    /* two_edge_connected_components {{{ */
+    /// Returns the components left after removing the bridges, and the bridge tree connecting them.
+    ///
+    /// Time complexity is O(V + E)
+    pub fn two_edge_connected_components(&self) -> TwoEdgeComponents {
+        let mut is_bridge = vec![false; self.edges.len()];
+        self.bridges.iter().for_each(|&id| is_bridge[id] = true);
+        let mut ids = vec![usize::MAX; self.n];
+        let mut groups = vec![];
+        for start in 0..self.n {
+            if ids[start] != usize::MAX {
+                continue;
+            }
+            let k = groups.len();
+            ids[start] = k;
+            let mut group = vec![start];
+            let mut i = 0;
+            while i < group.len() {
+                let v = group[i];
+                for &(to, id) in &self.adj[v] {
+                    if !is_bridge[id] && ids[to] == usize::MAX {
+                        ids[to] = k;
+                        group.push(to);
+                    }
+                }
+                i += 1;
+            }
+            groups.push(group);
+        }
+        let mut tree = vec![vec![]; groups.len()];
+        for &id in &self.bridges {
+            let (u, v) = self.edges[id];
+            tree[ids[u]].push(ids[v]);
+            tree[ids[v]].push(ids[u]);
+        }
+        return TwoEdgeComponents {ids, groups, tree};
+    }
+    /* }}} */
+
+
+    /* biconnected_components {{{ */
+    /// Returns the biconnected components and the block-cut tree.
+    /// An isolated vertex forms a component without edges, and self-loops belong to no component.
+    ///
+    /// Time complexity is O(V + E)
+    pub fn biconnected_components(&self) -> BiconnectedComponents {
+        let mut vertices = vec![];
+        let mut edges = vec![];
+        let mut seen = vec![usize::MAX; self.n];
+        for (k, block) in self.blocks.iter().enumerate() {
+            let mut vs = vec![];
+            for &id in block {
+                let (u, v) = self.edges[id];
+                for x in [u, v] {
+                    if seen[x] != k {
+                        seen[x] = k;
+                        vs.push(x);
+                    }
+                }
+            }
+            vertices.push(vs);
+            edges.push(block.clone());
+        }
+        for v in 0..self.n {
+            if seen[v] == usize::MAX {
+                vertices.push(vec![v]);
+                edges.push(vec![]);
+            }
+        }
+        let mut tree = vec![vec![]; self.n + vertices.len()];
+        for (k, vs) in vertices.iter().enumerate() {
+            for &v in vs {
+                tree[v].push(self.n + k);
+                tree[self.n + k].push(v);
+            }
+        }
+        return BiconnectedComponents {vertices, edges, tree};
+    }
+    /* }}} */
+}
+/* }}} */
+
+
+/* TwoEdgeComponents {{{ */
+/// 2-edge-connected components of an undirected graph.
+#[derive(Clone, Debug)]
+pub struct TwoEdgeComponents {
+    /// Component index of each vertex.
+    pub ids: Vec<usize>,
+    /// Vertices of each component.
+    pub groups: Vec<Vec<usize>>,
+    /// Adjacency lists of the bridge tree on the components, a forest if the graph is disconnected.
+    pub tree: Vec<Vec<usize>>,
+}
+/* }}} */
+
+
+/* BiconnectedComponents {{{ */
+/// Biconnected components of an undirected graph.
+#[derive(Clone, Debug)]
+pub struct BiconnectedComponents {
+    /// Vertices of each component.
+    pub vertices: Vec<Vec<usize>>,
+    /// Edge IDs of each component.
+    pub edges: Vec<Vec<usize>>,
+    /// Adjacency lists of the block-cut tree, where vertex `v` is `v` and component `k` is `n + k`.
+    /// Each vertex is adjacent to the components containing it, so only articulation points have several neighbours.
+    pub tree: Vec<Vec<usize>>,
+}
+/* }}} */
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+
+    fn count_components(n: usize, edges: &[(usize, usize)], removed_vertex: Option<usize>, removed_edge: Option<usize>) -> usize {
+        let mut dsu = ac_library::Dsu::new(n);
+        for (id, &(u, v)) in edges.iter().enumerate() {
+            if Some(id) != removed_edge && Some(u) != removed_vertex && Some(v) != removed_vertex {
+                dsu.merge(u, v);
+            }
+        }
+        return dsu.groups().len() - removed_vertex.is_some() as usize;
+    }
+
+    #[test]
+    fn sample() {
+        // 0 = 1 - 2 - 3
+        //         |   |
+        //         5 - 4 - 6
+        let edges = [(0, 1), (1, 0), (1, 2), (2, 3), (3, 4), (4, 5), (5, 2), (4, 6)];
+        let ll = Lowlink::new(7, &edges);
+        assert_eq!(ll.bridges, vec![2, 7]);
+        assert_eq!(ll.articulation_points, vec![1, 2, 4]);
+
+        let tecc = ll.two_edge_connected_components();
+        assert_eq!(tecc.groups.len(), 3);
+        assert_eq!(tecc.ids[0], tecc.ids[1]);
+        assert_eq!(tecc.ids[2], tecc.ids[5]);
+        assert_eq!(tecc.tree[tecc.ids[2]].len(), 2);
+
+        let bcc = ll.biconnected_components();
+        let mut blocks = bcc.edges.clone();
+        blocks.iter_mut().for_each(|b| b.sort());
+        blocks.sort();
+        assert_eq!(blocks, vec![vec![0, 1], vec![2], vec![3, 4, 5, 6], vec![7]]);
+        assert_eq!(bcc.tree.len(), 7 + 4);
+        assert_eq!(bcc.tree[2].len(), 2);
+        assert_eq!(bcc.tree[3].len(), 1);
+    }
+
+    #[test]
+    fn from_graph() {
+        let mut g = crate::graph::Graph::new(3);
+        let a = g.add_undirected_edge(0, 1, 1);
+        let b = g.add_undirected_edge(1, 2, 1);
+        g.add_undirected_edge(2, 1, 1);
+        let ll = Lowlink::from_graph(&g);
+        assert_eq!(ll.bridges, vec![a]);
+        assert_ne!(a, b);
+        assert_eq!(ll.articulation_points, vec![1]);
+    }
+
+    #[test]
+    fn random() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+        for _ in 0..200 {
+            let n = rng.gen_range(1..9);
+            let edges = (0..rng.gen_range(0..12)).map(|_| (rng.gen_range(0..n), rng.gen_range(0..n))).collect::<Vec<_>>();
+            let ll = Lowlink::new(n, &edges);
+            let base = count_components(n, &edges, None, None);
+
+            let bridges = (0..edges.len()).filter(|&id| count_components(n, &edges, None, Some(id)) > base).collect::<Vec<_>>();
+            assert_eq!(ll.bridges, bridges);
+            let articulation = (0..n).filter(|&v| count_components(n, &edges, Some(v), None) > base).collect::<Vec<_>>();
+            assert_eq!(ll.articulation_points, articulation);
+
+            let tecc = ll.two_edge_connected_components();
+            let without_bridges = edges.iter().enumerate().filter(|(id, _)| !bridges.contains(id)).map(|(_, &e)| e).collect::<Vec<_>>();
+            let mut dsu = ac_library::Dsu::new(n);
+            without_bridges.iter().for_each(|&(u, v)| { dsu.merge(u, v); });
+            for u in 0..n {
+                for v in 0..n {
+                    assert_eq!(tecc.ids[u] == tecc.ids[v], dsu.same(u, v));
+                }
+            }
+            assert_eq!(tecc.tree.iter().map(|a| a.len()).sum::<usize>(), 2 * bridges.len());
+
+            let bcc = ll.biconnected_components();
+            let mut covered = bcc.edges.concat();
+            covered.sort();
+            assert_eq!(covered, (0..edges.len()).filter(|&id| edges[id].0 != edges[id].1).collect::<Vec<_>>());
+            for (vs, es) in bcc.vertices.iter().zip(&bcc.edges) {
+                // A block stays connected after removing any one of its vertices.
+                if vs.len() >= 3 {
+                    let block = es.iter().map(|&id| edges[id]).collect::<Vec<_>>();
+                    for &x in vs {
+                        assert_eq!(count_components(n, &block, Some(x), None), n - vs.len() + 1);
+                    }
+                }
+            }
+            // Vertices shared by several blocks are exactly the articulation points.
+            let shared = (0..n).filter(|&v| bcc.tree[v].len() >= 2).collect::<Vec<_>>();
+            assert_eq!(shared, articulation);
+            assert_eq!(count_components(bcc.tree.len(), &bcc.tree.iter().enumerate().flat_map(|(u, a)| a.iter().map(move |&v| (u, v))).collect::<Vec<_>>(), None, None), base);
+        }
+    }
+
+    #[test]
+    fn deep() {
+        let n = 200_000;
+        let edges = (1..n).map(|v| (v - 1, v)).collect::<Vec<_>>();
+        let ll = Lowlink::new(n, &edges);
+        assert_eq!(ll.bridges.len(), n - 1);
+        assert_eq!(ll.articulation_points.len(), n - 2);
+        assert_eq!(ll.biconnected_components().vertices.len(), n - 1);
+    }
+}
@@ -0,0 +1,107 @@
+
+/* Add {{{ */
+/// Adds a constant to every element of a range, e.g. `LazySegtree<Add<Max<i64>>>`.
+/// Only valid for monoids where adding a constant commutes with the operation, such as `Max` and `Min`.
+pub struct Add<M>(std::marker::PhantomData<fn() -> M>);
+
+impl<M> ac_library::MapMonoid for Add<M>
+where
+    M: ac_library::Monoid,
+    M::S: Copy + std::ops::Add<Output = M::S> + num_traits::Zero,
+{
+    type M = M;
+    type F = M::S;
+
+    fn identity_map() -> Self::F {
+        num_traits::Zero::zero()
+    }
+
+    fn mapping(&f: &Self::F, &x: &<Self::M as ac_library::Monoid>::S) -> <Self::M as ac_library::Monoid>::S {
+        f + x
+    }
+
+    fn composition(&f: &Self::F, &g: &Self::F) -> Self::F {
+        f + g
+    }
+}
+/* }}} */
+
+
+/* Assign {{{ */
+/// Overwrites every element of a range, e.g. `LazySegtree<Assign<Min<i64>>>`.
+/// Only valid for idempotent monoids such as `Max` and `Min`.
+pub struct Assign<M>(std::marker::PhantomData<fn() -> M>);
+
+impl<M> ac_library::MapMonoid for Assign<M>
+where
+    M: ac_library::Monoid,
+{
+    type M = M;
+    type F = Option<M::S>;
+
+    fn identity_map() -> Self::F {
+        None
+    }
+
+    fn mapping(f: &Self::F, x: &<Self::M as ac_library::Monoid>::S) -> <Self::M as ac_library::Monoid>::S {
+        f.clone().unwrap_or_else(|| x.clone())
+    }
+
+    fn composition(f: &Self::F, g: &Self::F) -> Self::F {
+        f.clone().or_else(|| g.clone())
+    }
+}
+/* }}} */
+
+
+/* Reversed {{{ */
+/// Same maps as `F`, acting on `monoid::Reversed<F::M>`.
+/// Valid whenever `F` is, since its maps are homomorphisms of `F::M` and hence also of the reversed monoid.
+pub struct Reversed<F>(std::marker::PhantomData<fn() -> F>);
+
+impl<F: ac_library::MapMonoid> ac_library::MapMonoid for Reversed<F> {
+    type M = super::monoid::Reversed<F::M>;
+    type F = F::F;
+
+    fn identity_map() -> Self::F {
+        F::identity_map()
+    }
+
+    fn mapping(f: &Self::F, x: &<Self::M as ac_library::Monoid>::S) -> <Self::M as ac_library::Monoid>::S {
+        F::mapping(f, x)
+    }
+
+    fn composition(f: &Self::F, g: &Self::F) -> Self::F {
+        F::composition(f, g)
+    }
+}
+/* }}} */
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ac_library::{LazySegtree, Max, Min};
+
+    #[test]
+    fn add_max() {
+        let mut seg = LazySegtree::<Add<Max<i64>>>::from(vec![1, 5, 2, 4]);
+        seg.apply_range(0..2, 3);
+        assert_eq!(seg.prod(0..4), 8);
+        seg.apply_range(2..4, 10);
+        assert_eq!(seg.prod(0..2), 8);
+        assert_eq!(seg.prod(2..4), 14);
+        assert_eq!(seg.get(2), 12);
+    }
+
+    #[test]
+    fn assign_min() {
+        let mut seg = LazySegtree::<Assign<Min<i64>>>::from(vec![3, 1, 4, 1, 5]);
+        seg.apply_range(1..4, Some(7));
+        assert_eq!(seg.prod(0..5), 3);
+        assert_eq!(seg.prod(1..4), 7);
+        seg.apply_range(2..3, Some(0));
+        assert_eq!(seg.prod(1..5), 0);
+        assert_eq!(seg.get(3), 7);
+    }
+}
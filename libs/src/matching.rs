@@ -0,0 +1,260 @@
+
+/* GeneralMatching {{{ */
+/// Matching on a graph that need not be bipartite, treating every edge as undirected.
+/// Use `flow::BipartiteMatching` for bipartite graphs, which is faster.
+#[derive(Clone, Debug)]
+pub struct GeneralMatching {
+    pub n: usize,
+    /// Vertex matched to each vertex.
+    pub mate: Vec<Option<usize>>,
+}
+
+impl GeneralMatching {
+    /* new {{{ */
+    /// Finds a maximum cardinality matching of `g` with Edmonds' blossom algorithm.
+    /// Each search grows an alternating tree by BFS from a free vertex and contracts odd cycles (blossoms) into their base.
+    ///
+    /// Time complexity is O(V^3)
+    pub fn new(g: &impl super::graph::Adjacency) -> Self {
+        let n = g.num_vertices();
+        let mut adj = vec![vec![]; n];
+        for e in g.edge_list() {
+            if e.from != e.to {
+                adj[e.from].push(e.to);
+                adj[e.to].push(e.from);
+            }
+        }
+        const NONE: usize = usize::MAX;
+        let mut mate = vec![NONE; n];
+        for root in 0..n {
+            if mate[root] != NONE {
+                continue;
+            }
+            // `parent[v]` is the vertex before `v` on the alternating path from the root, for `v` at odd distance.
+            let mut parent = vec![NONE; n];
+            let mut base = (0..n).collect::<Vec<_>>();
+            let mut used = vec![false; n];
+            used[root] = true;
+            let mut que = std::collections::VecDeque::from([root]);
+            let mut free = None;
+            'search: while let Some(v) = que.pop_front() {
+                for &to in &adj[v] {
+                    if base[v] == base[to] || mate[v] == to {
+                        continue;
+                    }
+                    if to == root || (mate[to] != NONE && parent[mate[to]] != NONE) {
+                        // `v` and `to` are both at even distance, so the edge closes a blossom.
+                        let lca = {
+                            let mut seen = vec![false; n];
+                            let mut a = v;
+                            loop {
+                                a = base[a];
+                                seen[a] = true;
+                                if mate[a] == NONE {
+                                    break;
+                                }
+                                a = parent[mate[a]];
+                            }
+                            let mut b = to;
+                            loop {
+                                b = base[b];
+                                if seen[b] {
+                                    break b;
+                                }
+                                b = parent[mate[b]];
+                            }
+                        };
+                        let mut blossom = vec![false; n];
+                        for (mut x, mut child) in [(v, to), (to, v)] {
+                            while base[x] != lca {
+                                blossom[base[x]] = true;
+                                blossom[base[mate[x]]] = true;
+                                parent[x] = child;
+                                child = mate[x];
+                                x = parent[mate[x]];
+                            }
+                        }
+                        for x in 0..n {
+                            if blossom[base[x]] {
+                                base[x] = lca;
+                                if !used[x] {
+                                    used[x] = true;
+                                    que.push_back(x);
+                                }
+                            }
+                        }
+                    } else if parent[to] == NONE {
+                        parent[to] = v;
+                        if mate[to] == NONE {
+                            free = Some(to);
+                            break 'search;
+                        }
+                        used[mate[to]] = true;
+                        que.push_back(mate[to]);
+                    }
+                }
+            }
+            // Flip the matching along the augmenting path ending at `free`.
+            let mut v = free.unwrap_or(NONE);
+            while v != NONE {
+                let pv = parent[v];
+                let next = mate[pv];
+                mate[v] = pv;
+                mate[pv] = v;
+                v = next;
+            }
+        }
+        return Self {n, mate: mate.into_iter().map(|v| if v == NONE { None } else { Some(v) }).collect()};
+    }
+    /* }}} */
+
+
+    /* max_weight {{{ */
+    /// Returns the maximum total weight of a matching of `g` with such a matching, for small V.
+    /// Edges of negative weight are never worth taking.
+    ///
+    /// Time complexity is O(2^V V + E)
+    pub fn max_weight<G: super::graph::Adjacency>(g: &G) -> (G::W, Self) {
+        use super::graph::Weight;
+        let n = g.num_vertices();
+        // Heaviest edge between each pair of vertices.
+        let mut best: Vec<Vec<Option<G::W>>> = vec![vec![None; n]; n];
+        for e in g.edge_list() {
+            if e.from != e.to && best[e.from][e.to].map_or(true, |w| e.weight.compare(&w).is_gt()) {
+                best[e.from][e.to] = Some(e.weight);
+                best[e.to][e.from] = Some(e.weight);
+            }
+        }
+        // `dp[mask]` is the best weight once the vertices in `mask` are decided, always deciding the smallest undecided vertex next.
+        let full = (1_usize << n) - 1;
+        let mut dp: Vec<Option<G::W>> = vec![None; full + 1];
+        let mut choice = vec![(0, None); full + 1];
+        dp[0] = Some(G::W::zero());
+        let relax = |dp: &mut Vec<Option<G::W>>, choice: &mut Vec<(usize, Option<usize>)>, to: usize, w: G::W, from: (usize, Option<usize>)| {
+            if dp[to].map_or(true, |x| w.compare(&x).is_gt()) {
+                dp[to] = Some(w);
+                choice[to] = from;
+            }
+        };
+        for mask in 0..full {
+            let Some(d) = dp[mask] else { continue };
+            let i = (!mask).trailing_zeros() as usize;
+            relax(&mut dp, &mut choice, mask | 1 << i, d, (mask, None));
+            for j in i + 1..n {
+                if let Some(w) = best[i][j].filter(|_| mask >> j & 1 == 0) {
+                    relax(&mut dp, &mut choice, mask | 1 << i | 1 << j, d.plus(w), (mask, Some(j)));
+                }
+            }
+        }
+        let mut mate = vec![None; n];
+        let mut mask = full;
+        while mask != 0 {
+            let (prev, j) = choice[mask];
+            if let Some(j) = j {
+                let i = (!prev).trailing_zeros() as usize;
+                mate[i] = Some(j);
+                mate[j] = Some(i);
+            }
+            mask = prev;
+        }
+        return (dp[full].unwrap(), Self {n, mate});
+    }
+    /* }}} */
+
+
+    /// Returns the number of matched pairs.
+    pub fn size(&self) -> usize {
+        return self.mate.iter().flatten().count() / 2;
+    }
+
+    /// Returns the matched pairs `(u, v)` with `u < v`, in increasing order of `u`.
+    pub fn pairs(&self) -> Vec<(usize, usize)> {
+        return (0..self.n).filter_map(|u| self.mate[u].filter(|&v| u < v).map(|v| (u, v))).collect();
+    }
+}
+/* }}} */
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Graph;
+    use rand::{Rng, SeedableRng};
+
+    /// Returns the maximum weight of a set of edges without a common endpoint, trying every subset.
+    fn brute(g: &Graph) -> (usize, i64) {
+        let m = g.edges.len();
+        let mut res = (0, 0);
+        for mask in 0..1_usize << m {
+            let mut used = vec![false; g.n];
+            let mut weight = 0;
+            let ok = g.edges.iter().filter(|e| mask >> e.id & 1 == 1).all(|e| {
+                weight += e.weight;
+                let free = e.from != e.to && !used[e.from] && !used[e.to];
+                used[e.from] = true;
+                used[e.to] = true;
+                free
+            });
+            if ok {
+                res = (res.0.max(mask.count_ones() as usize), res.1.max(weight));
+            }
+        }
+        return res;
+    }
+
+    fn check(g: &Graph, m: &GeneralMatching) {
+        for (u, v) in m.pairs() {
+            assert_eq!(m.mate[v], Some(u));
+            assert!(g.edges.iter().any(|e| (e.from, e.to) == (u, v) || (e.from, e.to) == (v, u)));
+        }
+        assert_eq!(m.pairs().len(), m.size());
+    }
+
+    #[test]
+    fn blossom() {
+        // Odd cycle 0-1-2-3-4 with a tail 4-5, which needs a contraction to match every vertex.
+        let mut g = Graph::new(6);
+        for (u, v) in [(0, 1), (1, 2), (2, 3), (3, 4), (4, 0), (4, 5)] {
+            g.add_edge(u, v, 1);
+        }
+        let m = GeneralMatching::new(&g);
+        assert_eq!(m.size(), 3);
+        check(&g, &m);
+
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+        for _ in 0..300 {
+            let n = rng.gen_range(1..9);
+            let mut g = Graph::new(n);
+            for _ in 0..rng.gen_range(0..13) {
+                g.add_edge(rng.gen_range(0..n), rng.gen_range(0..n), rng.gen_range(-3..10));
+            }
+            let (size, weight) = brute(&g);
+            let m = GeneralMatching::new(&g);
+            check(&g, &m);
+            assert_eq!(m.size(), size);
+            let (w, wm) = GeneralMatching::max_weight(&g);
+            check(&g, &wm);
+            assert_eq!(w, weight);
+            let total = wm.pairs().iter().map(|&(u, v)| g.edges.iter().filter(|e| (e.from, e.to) == (u, v) || (e.from, e.to) == (v, u)).map(|e| e.weight).max().unwrap()).sum::<i64>();
+            assert_eq!(total, weight);
+        }
+    }
+
+    #[test]
+    fn dense() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(1);
+        let n = 300;
+        let mut g = Graph::new(n);
+        for u in 0..n {
+            for v in u + 1..n {
+                if rng.gen_ratio(1, 20) {
+                    g.add_edge(u, v, 1);
+                }
+            }
+        }
+        let m = GeneralMatching::new(&g);
+        check(&g, &m);
+        // With about 15 neighbours per vertex, some perfect matching exists with overwhelming probability.
+        assert_eq!(m.size(), n / 2);
+    }
+}
@@ -0,0 +1,379 @@
+
+/* MinCostFlow {{{ */
+#[derive(Clone, Debug)]
+struct Arc {
+    to: usize,
+    rev: usize,
+    cap: i64,
+    cost: i64,
+}
+
+/// Minimum cost flow by successive shortest paths with Dijkstra on reduced costs.
+///
+/// Unlike `ac_library::MinCostFlowGraph`, costs may be negative as long as no cycle of positive capacity has a negative total cost:
+/// the potentials are initialised with Bellman–Ford whenever a residual edge has a negative cost.
+#[derive(Clone, Debug)]
+pub struct MinCostFlow {
+    pub n: usize,
+    g: Vec<Vec<Arc>>,
+    /// `(from, index in g[from])` of each arc.
+    pos: Vec<(usize, usize)>,
+    /// Edge ID of each arc and whether it runs from `from` to `to` of the edge.
+    ids: Vec<(usize, bool)>,
+    num_edges: usize,
+}
+
+impl MinCostFlow {
+    pub fn new(n: usize) -> Self {
+        return Self {n, g: vec![vec![]; n], pos: vec![], ids: vec![], num_edges: 0};
+    }
+
+    fn add_arc(&mut self, from: usize, to: usize, cap: i64, cost: i64, id: (usize, bool)) {
+        assert!(from != to && 0 <= cap);
+        self.pos.push((from, self.g[from].len()));
+        self.ids.push(id);
+        let rev = self.g[to].len();
+        self.g[from].push(Arc {to, rev, cap, cost});
+        let rev = self.g[from].len() - 1;
+        self.g[to].push(Arc {to: from, rev, cap: 0, cost: -cost});
+    }
+
+    /// Adds an edge and returns its ID.
+    pub fn add_edge(&mut self, from: usize, to: usize, cap: i64, cost: i64) -> usize {
+        let id = self.num_edges;
+        self.num_edges += 1;
+        self.add_arc(from, to, cap, cost, (id, true));
+        return id;
+    }
+
+
+    /* from_graph {{{ */
+    /// Builds the network of `g` whose weights are `(capacity, cost)`, with the same edge IDs.
+    /// An undirected edge can carry flow either way, up to its capacity in each direction.
+    /// Self-loops are left out.
+    ///
+    /// Time complexity is O(V + E)
+    pub fn from_graph(g: &impl super::graph::Adjacency<W = (i64, i64)>) -> Self {
+        let mut res = Self::new(g.num_vertices());
+        res.num_edges = g.edge_list().len();
+        for e in g.edge_list() {
+            if e.from != e.to {
+                res.add_arc(e.from, e.to, e.weight.0, e.weight.1, (e.id, true));
+            }
+        }
+        for v in 0..g.num_vertices() {
+            for &(id, to, (cap, cost)) in g.out_edges(v) {
+                // The second direction of an undirected edge.
+                if v != g.edge_list()[id].from {
+                    res.add_arc(v, to, cap, cost, (id, false));
+                }
+            }
+        }
+        return res;
+    }
+    /* }}} */
+
+
+    /* potentials {{{ */
+    /// Returns potentials making every residual reduced cost reachable from `s` non-negative.
+    ///
+    /// Time complexity is O(VE) if some residual edge has a negative cost, and O(V + E) otherwise
+    fn potentials(&self, s: usize) -> Vec<i64> {
+        if self.g.iter().flatten().all(|a| a.cap == 0 || a.cost >= 0) {
+            return vec![0; self.n];
+        }
+        let mut dist = vec![i64::MAX; self.n];
+        dist[s] = 0;
+        for round in 0..self.n {
+            let mut updated = false;
+            for v in 0..self.n {
+                if dist[v] == i64::MAX {
+                    continue;
+                }
+                for a in &self.g[v] {
+                    if a.cap > 0 && dist[v] + a.cost < dist[a.to] {
+                        dist[a.to] = dist[v] + a.cost;
+                        updated = true;
+                    }
+                }
+            }
+            if !updated {
+                break;
+            }
+            assert!(round + 1 < self.n, "there is a negative cycle");
+        }
+        return dist.into_iter().map(|d| if d == i64::MAX { 0 } else { d }).collect();
+    }
+    /* }}} */
+
+
+    /* slope {{{ */
+    /// Pushes up to `limit` units from `s` to `t` at minimum cost,
+    /// and returns the `(flow, cost)` breakpoints of the piecewise linear cost as a function of the flow, starting with `(0, 0)`.
+    /// The flow is kept, so another call pushes more on top of it.
+    ///
+    /// Time complexity is O(F (V + E) log V) plus the initial Bellman–Ford, where F is the number of augmentations
+    pub fn slope(&mut self, s: usize, t: usize, limit: i64) -> Vec<(i64, i64)> {
+        assert!(s != t);
+        let mut potential = self.potentials(s);
+        let mut res = vec![(0, 0)];
+        let (mut flow, mut cost) = (0, 0);
+        let mut prev_slope = None;
+        while flow < limit {
+            let mut dist = vec![i64::MAX; self.n];
+            let mut prev = vec![(usize::MAX, usize::MAX); self.n];
+            let mut que = std::collections::BinaryHeap::from([(std::cmp::Reverse(0), s)]);
+            dist[s] = 0;
+            while let Some((std::cmp::Reverse(d), v)) = que.pop() {
+                if dist[v] != d {
+                    continue;
+                }
+                for (i, a) in self.g[v].iter().enumerate() {
+                    let nd = d + a.cost + potential[v] - potential[a.to];
+                    if a.cap > 0 && nd < dist[a.to] {
+                        dist[a.to] = nd;
+                        prev[a.to] = (v, i);
+                        que.push((std::cmp::Reverse(nd), a.to));
+                    }
+                }
+            }
+            if dist[t] == i64::MAX {
+                break;
+            }
+            for v in 0..self.n {
+                if dist[v] != i64::MAX {
+                    potential[v] += dist[v];
+                }
+            }
+            let mut push = limit - flow;
+            let mut v = t;
+            while v != s {
+                let (u, i) = prev[v];
+                push = push.min(self.g[u][i].cap);
+                v = u;
+            }
+            let mut v = t;
+            while v != s {
+                let (u, i) = prev[v];
+                self.g[u][i].cap -= push;
+                let rev = self.g[u][i].rev;
+                self.g[v][rev].cap += push;
+                v = u;
+            }
+            // The cost of a unit along the path, since the potential of `s` stays 0.
+            let unit = potential[t] - potential[s];
+            flow += push;
+            cost += unit * push;
+            if prev_slope == Some(unit) {
+                res.pop();
+            }
+            res.push((flow, cost));
+            prev_slope = Some(unit);
+        }
+        return res;
+    }
+    /* }}} */
+
+
+    /// Same as `slope`, returning only the total `(flow, cost)`.
+    pub fn flow(&mut self, s: usize, t: usize, limit: i64) -> (i64, i64) {
+        return self.slope(s, t, limit).pop().unwrap();
+    }
+
+    /// Returns the minimum cost to send exactly `k` units from `s` to `t`, or `None` if at most fewer can be sent.
+    /// With negative costs, this may cost more than sending fewer.
+    pub fn min_cost_exact(&mut self, s: usize, t: usize, k: i64) -> Option<i64> {
+        let (flow, cost) = self.flow(s, t, k);
+        return if flow == k { Some(cost) } else { None };
+    }
+
+    /// Returns the flow along each edge by ID, which is negative if an undirected edge carries it backwards.
+    ///
+    /// Time complexity is O(E)
+    pub fn edge_flows(&self) -> Vec<i64> {
+        let mut res = vec![0; self.num_edges];
+        for (i, &(from, j)) in self.pos.iter().enumerate() {
+            let a = &self.g[from][j];
+            let flow = self.g[a.to][a.rev].cap;
+            let (id, forward) = self.ids[i];
+            res[id] += if forward { flow } else { -flow };
+        }
+        return res;
+    }
+}
+/* }}} */
+
+
+/* hungarian {{{ */
+/// Returns the minimum total cost of assigning every row of `cost` to a distinct column, with the column of each row.
+/// There must be at most as many rows as columns. Costs may be negative or floating point.
+///
+/// Time complexity is O(N^2 M) for N rows and M columns
+pub fn hungarian<T>(cost: &[Vec<T>]) -> (T, Vec<usize>)
+where
+    T: num_traits::Num + num_traits::Bounded + PartialOrd + Copy,
+{
+    let n = cost.len();
+    let m = cost.first().map_or(0, |row| row.len());
+    assert!(n <= m, "there are more rows than columns");
+    // Row potentials `u`, column potentials `v` and the row assigned to each column, 1-indexed with column 0 as a sentinel.
+    let mut u = vec![T::zero(); n + 1];
+    let mut v = vec![T::zero(); m + 1];
+    let mut row = vec![0; m + 1];
+    let mut way = vec![0; m + 1];
+    for i in 1..=n {
+        row[0] = i;
+        let mut j0 = 0;
+        let mut min = vec![T::max_value(); m + 1];
+        let mut used = vec![false; m + 1];
+        // Grow a tree of tight edges from row `i` until it reaches a free column.
+        loop {
+            used[j0] = true;
+            let i0 = row[j0];
+            let mut delta = T::max_value();
+            let mut j1 = 0;
+            for j in 1..=m {
+                if !used[j] {
+                    let cur = cost[i0 - 1][j - 1] - u[i0] - v[j];
+                    if cur < min[j] {
+                        min[j] = cur;
+                        way[j] = j0;
+                    }
+                    if min[j] < delta {
+                        delta = min[j];
+                        j1 = j;
+                    }
+                }
+            }
+            for j in 0..=m {
+                if used[j] {
+                    u[row[j]] = u[row[j]] + delta;
+                    v[j] = v[j] - delta;
+                } else {
+                    min[j] = min[j] - delta;
+                }
+            }
+            j0 = j1;
+            if row[j0] == 0 {
+                break;
+            }
+        }
+        // Flip the assignments along the alternating path.
+        while j0 != 0 {
+            let j1 = way[j0];
+            row[j0] = row[j1];
+            j0 = j1;
+        }
+    }
+    let mut assignment = vec![0; n];
+    for j in 1..=m {
+        if row[j] != 0 {
+            assignment[row[j] - 1] = j - 1;
+        }
+    }
+    let total = (0..n).fold(T::zero(), |acc, i| acc + cost[i][assignment[i]]);
+    return (total, assignment);
+}
+/* }}} */
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Graph;
+    use itertools::Itertools;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn against_ac_library() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+        for _ in 0..100 {
+            let n = rng.gen_range(2..8);
+            let mut g = Graph::new(n);
+            let mut ac = ac_library::MinCostFlowGraph::new(n);
+            for _ in 0..rng.gen_range(0..20) {
+                let (u, v, cap, cost) = (rng.gen_range(0..n), rng.gen_range(0..n), rng.gen_range(0..5), rng.gen_range(0..10));
+                g.add_edge(u, v, (cap, cost));
+                if u != v {
+                    ac.add_edge(u, v, cap, cost);
+                }
+            }
+            let mut mcf = MinCostFlow::from_graph(&g);
+            let limit = rng.gen_range(0..10);
+            let slope = mcf.slope(0, n - 1, limit);
+            assert_eq!(slope, ac.slope(0, n - 1, limit));
+            let flows = mcf.edge_flows();
+            assert_eq!(g.edges.iter().map(|e| e.weight.1 * flows[e.id]).sum::<i64>(), slope.last().unwrap().1);
+        }
+    }
+
+    #[test]
+    fn negative_costs() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(1);
+        for _ in 0..200 {
+            let n = rng.gen_range(2..5);
+            // Edges only go to larger vertices, so there is no cycle.
+            let edges = (0..rng.gen_range(0..6)).map(|_| {
+                let u = rng.gen_range(0..n - 1);
+                (u, rng.gen_range(u + 1..n), rng.gen_range(0..3_i64), rng.gen_range(-5..5_i64))
+            }).collect::<Vec<_>>();
+            let (s, t) = (0, n - 1);
+            // Minimum cost of each flow value over every assignment conserving flow except at s and t.
+            // Sending nothing costs nothing, which `multi_cartesian_product` misses when there are no edges.
+            let mut best = std::collections::BTreeMap::from([(0, 0)]);
+            for flows in edges.iter().map(|e| 0..=e.2).multi_cartesian_product() {
+                let mut balance = vec![0; n];
+                for (e, &f) in edges.iter().zip(&flows) {
+                    balance[e.0] -= f;
+                    balance[e.1] += f;
+                }
+                if (1..n - 1).all(|v| balance[v] == 0) {
+                    let cost = edges.iter().zip(&flows).map(|(e, f)| e.3 * f).sum::<i64>();
+                    let entry = best.entry(balance[t]).or_insert(cost);
+                    *entry = cost.min(*entry);
+                }
+            }
+            for k in 0..=4 {
+                let mut mcf = MinCostFlow::new(n);
+                for &(u, v, cap, cost) in &edges {
+                    mcf.add_edge(u, v, cap, cost);
+                }
+                let res = mcf.min_cost_exact(s, t, k);
+                assert_eq!(res, best.get(&k).copied());
+                let flows = mcf.edge_flows();
+                if let Some(cost) = res {
+                    assert_eq!(edges.iter().zip(&flows).map(|(e, f)| e.3 * f).sum::<i64>(), cost);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn undirected() {
+        let mut g = Graph::new(3);
+        g.add_undirected_edge(1, 0, (2, 1));
+        g.add_undirected_edge(2, 1, (1, 1));
+        g.add_edge(0, 2, (1, 5));
+        let mut mcf = MinCostFlow::from_graph(&g);
+        assert_eq!(mcf.slope(0, 2, 5), vec![(0, 0), (1, 2), (2, 7)]);
+        assert_eq!(mcf.edge_flows(), vec![-1, -1, 1]);
+    }
+
+    #[test]
+    fn hungarian() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(2);
+        for _ in 0..200 {
+            let n = rng.gen_range(0..6);
+            let m = rng.gen_range(n.max(1)..7);
+            let cost = (0..n).map(|_| (0..m).map(|_| rng.gen_range(-20..20_i64)).collect()).collect::<Vec<Vec<_>>>();
+            let (total, assignment) = super::hungarian(&cost);
+            assert_eq!(total, (0..n).map(|i| cost[i][assignment[i]]).sum::<i64>());
+            assert!(assignment.iter().all_unique());
+            let naive = (0..m).permutations(n).map(|p| (0..n).map(|i| cost[i][p[i]]).sum::<i64>()).min().unwrap();
+            assert_eq!(total, naive);
+
+            let float = cost.iter().map(|row| row.iter().map(|&c| c as f64 / 4.0).collect()).collect::<Vec<Vec<_>>>();
+            assert_eq!(super::hungarian(&float).0, naive as f64 / 4.0);
+        }
+    }
+}
@@ -0,0 +1,110 @@
+
+/* Xor {{{ */
+/// Monoid of bitwise xor, e.g. `Segtree<Xor<u64>>`.
+pub struct Xor<S>(std::marker::PhantomData<fn() -> S>);
+
+impl<S> ac_library::Monoid for Xor<S>
+where
+    S: Copy + std::ops::BitXor<Output = S> + num_traits::Zero,
+{
+    type S = S;
+
+    fn identity() -> Self::S {
+        S::zero()
+    }
+
+    fn binary_operation(a: &Self::S, b: &Self::S) -> Self::S {
+        *a ^ *b
+    }
+}
+/* }}} */
+
+
+/* Affine {{{ */
+/// Monoid of affine maps `x -> a * x + b` stored as `(a, b)`.
+/// The product `f * g` applies `f` first and then `g`, so this monoid is not commutative.
+pub struct Affine<S>(std::marker::PhantomData<fn() -> S>);
+
+impl<S> ac_library::Monoid for Affine<S>
+where
+    S: Copy + std::ops::Add<Output = S> + std::ops::Mul<Output = S> + num_traits::Zero + num_traits::One,
+{
+    type S = (S, S);
+
+    fn identity() -> Self::S {
+        (S::one(), S::zero())
+    }
+
+    fn binary_operation(&(a, b): &Self::S, &(c, d): &Self::S) -> Self::S {
+        (a * c, b * c + d)
+    }
+}
+/* }}} */
+
+
+/* Trivial {{{ */
+/// Monoid with the single element `()`, for structures that take a monoid but need no values.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Trivial;
+
+impl ac_library::Monoid for Trivial {
+    type S = ();
+
+    fn identity() -> Self::S {}
+
+    fn binary_operation(_: &Self::S, _: &Self::S) -> Self::S {}
+}
+/* }}} */
+
+
+/* Reversed {{{ */
+/// Monoid `M` with the operands swapped, so a product over a range is the product of `M` read backwards.
+pub struct Reversed<M>(std::marker::PhantomData<fn() -> M>);
+
+impl<M: ac_library::Monoid> ac_library::Monoid for Reversed<M> {
+    type S = M::S;
+
+    fn identity() -> Self::S {
+        M::identity()
+    }
+
+    fn binary_operation(a: &Self::S, b: &Self::S) -> Self::S {
+        M::binary_operation(b, a)
+    }
+}
+/* }}} */
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ac_library::{Monoid, Segtree};
+
+    #[test]
+    fn xor() {
+        let seg = Segtree::<Xor<u64>>::from(vec![1, 2, 4, 8, 3]);
+        assert_eq!(seg.prod(0..5), 12);
+        assert_eq!(seg.prod(1..3), 6);
+        assert_eq!(seg.prod(2..2), 0);
+    }
+
+    #[test]
+    fn affine() {
+        let f = (2, 1);
+        let g = (3, 4);
+        // g(f(x)) = 3 * (2x + 1) + 4 = 6x + 7
+        assert_eq!(Affine::<i64>::binary_operation(&f, &g), (6, 7));
+        assert_eq!(Affine::<i64>::binary_operation(&g, &f), (6, 9));
+        assert_eq!(Affine::<i64>::binary_operation(&Affine::<i64>::identity(), &f), f);
+
+        let seg = Segtree::<Affine<i64>>::from(vec![(2, 1), (3, 4), (1, -2)]);
+        assert_eq!(seg.all_prod(), (6, 5));
+    }
+
+    #[test]
+    fn reversed() {
+        let seg = Segtree::<Reversed<Affine<i64>>>::from(vec![(2, 1), (3, 4), (1, -2)]);
+        // Applies x -> x - 2, then x -> 3x + 4, then x -> 2x + 1.
+        assert_eq!(seg.all_prod(), (6, -3));
+    }
+}
@@ -1,22 +1,24 @@
 
 /* MonoidDsu {{{ */
+/// Disjoint set union where each set holds the product of its elements' values under a monoid `M`.
+/// `M` should be commutative because the order of merged values is unspecified.
 #[derive(Debug, Clone)]
-#[allow(dead_code)]
-struct MonoidDsu<M: ac_library::Monoid> {
+pub struct MonoidDsu<M: ac_library::Monoid> {
     n: usize,
     parent_or_size: Vec<i32>,
     data: Vec<M::S>,
 }
 
-#[allow(dead_code)]
 impl <M: ac_library::Monoid> MonoidDsu<M> {
-    fn new(data: Vec<M::S>) -> Self {
+    /// Creates `data.len()` singleton sets, where set `i` holds `data[i]`.
+    pub fn new(data: Vec<M::S>) -> Self {
         let n = data.len();
         let parent_or_size = vec![-1; n];
         Self { n, parent_or_size, data }
     }
 
-    fn merge(&mut self, x: usize, y: usize) -> usize {
+    /// Merges the sets of `x` and `y` and returns the new leader.
+    pub fn merge(&mut self, x: usize, y: usize) -> usize {
         assert!(x < self.n && y < self.n);
         let (mut x, mut y) = (self.leader(x), self.leader(y));
         if x == y {
@@ -32,12 +34,12 @@ impl <M: ac_library::Monoid> MonoidDsu<M> {
         x
     }
 
-    fn same(&mut self, x: usize, y: usize) -> bool {
+    pub fn same(&mut self, x: usize, y: usize) -> bool {
         assert!(x < self.n && y < self.n);
         self.leader(x) == self.leader(y)
     }
 
-    fn leader(&mut self, x: usize) -> usize {
+    pub fn leader(&mut self, x: usize) -> usize {
         assert!(x < self.n);
         if self.parent_or_size[x] < 0 {
             return x;
@@ -46,13 +48,13 @@ impl <M: ac_library::Monoid> MonoidDsu<M> {
         self.parent_or_size[x] as usize
     }
 
-    fn size(&mut self, x: usize) -> usize {
+    pub fn size(&mut self, x: usize) -> usize {
         assert!(x < self.n);
         let x = self.leader(x);
         -self.parent_or_size[x] as usize
     }
 
-    fn groups(&mut self) -> Vec<Vec<usize>> {
+    pub fn groups(&mut self) -> Vec<Vec<usize>> {
         let mut leader_buf = vec![0; self.n];
         let mut group_size = vec![0; self.n];
         for i in 0..self.n {
@@ -66,10 +68,46 @@ impl <M: ac_library::Monoid> MonoidDsu<M> {
         result.into_iter().filter(|x| !x.is_empty()).collect()
     }
 
-    fn get_val(&mut self, x: usize) -> M::S {
+    /// Returns the product of the values in the set of `x`.
+    pub fn get_val(&mut self, x: usize) -> M::S {
         assert!(x < self.n);
         let x = self.leader(x);
         self.data[x].clone()
     }
 }
 /* }}} */
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ac_library::{Additive, Max};
+
+    #[test]
+    fn merge_and_get_val() {
+        let mut dsu = MonoidDsu::<Additive<i64>>::new(vec![1, 2, 3, 4, 5]);
+        dsu.merge(0, 1);
+        dsu.merge(3, 4);
+        assert_eq!(dsu.get_val(1), 3);
+        assert_eq!(dsu.get_val(2), 3);
+        assert_eq!(dsu.get_val(4), 9);
+        assert!(!dsu.same(0, 4));
+
+        dsu.merge(1, 4);
+        assert!(dsu.same(0, 3));
+        assert_eq!(dsu.size(3), 4);
+        assert_eq!(dsu.get_val(0), 12);
+        assert_eq!(dsu.merge(0, 3), dsu.leader(4));
+        assert_eq!(dsu.get_val(0), 12);
+        assert_eq!(dsu.groups(), vec![vec![0, 1, 3, 4], vec![2]]);
+    }
+
+    #[test]
+    fn max() {
+        let mut dsu = MonoidDsu::<Max<i32>>::new(vec![5, 1, 7]);
+        dsu.merge(0, 1);
+        assert_eq!(dsu.get_val(1), 5);
+        dsu.merge(2, 1);
+        assert_eq!(dsu.get_val(0), 7);
+    }
+}
@@ -0,0 +1,539 @@
+
+/* modmul {{{ */
+/// Returns a * b mod m without overflow.
+pub fn modmul(a: i64, b: i64, m: i64) -> i64 {
+    return ((a as i128 * b as i128) % m as i128) as i64;
+}
+/* }}} */
+
+
+/* modpow {{{ */
+/// Returns a^n mod m.
+///
+/// Time complexity is O(log n)
+pub fn modpow(mut a: i64, mut n: i64, m: i64) -> i64 {
+    let mut res = 1;
+    while n > 0 {
+        if n & 1 == 1 {
+            res = modmul(a, res, m);
+        }
+        a = modmul(a, a, m);
+        n >>= 1;
+    }
+    return res;
+}
+/* }}} */
+
+
+/* modinv {{{ */
+/// Returns the inverse of a mod m by the extended Euclidean algorithm.
+/// a and m must be coprime.
+///
+/// Time complexity is O(log m)
+pub fn modinv(mut a: i64, m: i64) -> i64 {
+    let mut b = m;
+    let mut u = 1;
+    let mut v = 0;
+    while b > 0 {
+        let t = a / b;
+        a -= t * b;
+        std::mem::swap(&mut a, &mut b);
+        u -= t * v;
+        std::mem::swap(&mut u, &mut v);
+    }
+    u %= m;
+    if u < 0 {
+        u += m;
+    }
+    return u;
+}
+/* }}} */
+
+
+/* eratosthenes {{{ */
+/// Returns the primes up to n by the sieve of Eratosthenes.
+///
+/// Time complexity is O(n log log n)
+pub fn eratosthenes(n: u64) -> Vec<u64> {
+    let mut is_prime = vec![true; n as usize + 1];
+    let mut primes = vec![];
+    for i in 2..=n {
+        if is_prime[i as usize] {
+            for j in ((i * i)..=n).step_by(i as usize) {
+                is_prime[j as usize] = false;
+            }
+            primes.push(i);
+        }
+    }
+    return primes;
+}
+/* }}} */
+
+
+/* is_prime {{{ */
+/// Trial division
+///
+/// Time complexity is O(n^(1/2))
+pub fn is_prime(n: i64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    let mut i = 2;
+    while i * i <= n {
+        if n % i == 0 {
+            return false;
+        }
+        i += 1;
+    }
+    return true;
+}
+/* }}} */
+
+
+/* fast_is_prime {{{ */
+/// Miller-Rabin primality test
+///
+/// Time complexity is O(log n)
+pub fn fast_is_prime(n: i64) -> bool {
+    if n == 2 { return true; }
+    if n < 2 || n & 1 == 0 { return false; }
+    let n1 = n - 1;
+    let s = n1.trailing_zeros();
+    let d = n1 >> s;
+    [2, 325, 9375, 28178, 450775, 9780504, 1795265022].iter().all(|&base| {
+        let a = if base < n { 
+            base 
+        } else { 
+            base % n 
+        };
+        if a == 0 { 
+            return true; 
+        }
+        let mut t = modpow(a, d, n);
+        if t == 1 || t == n1 {
+            return true; 
+        }
+        for _ in 1..s {
+            t = modmul(t, t, n); 
+            if t == n1 { 
+                return true; 
+            } 
+        }
+        false
+    })
+}
+/* }}} */
+
+
+/* find_factor {{{ */
+/// Returns a nontrivial factor of a composite n by Pollard's rho algorithm.
+pub fn find_factor(n: i64) -> i64 {
+    if n & 1 == 0 {
+        return 2;
+    }
+    let mut x = 0;
+    let mut y = 0;
+    let mut prod = 1;
+    let f = |o| modmul(o, o, n) + 1;
+    let mut t = 30;
+    let mut z = 0;
+    while t % 64 != 0 || num::integer::gcd(prod, n) == 1 {
+        if x == y {
+            z += 1;
+            x = z;
+            y = f(x);
+        }
+        let q = modmul(prod, x + n - y, n);
+        if q != 0 {
+            prod = q;
+        }
+        x = f(x);
+        y = f(f(y));
+        t += 1;
+    }
+    return num::integer::gcd(prod, n);
+}
+/* }}} */
+
+
+/* factorize {{{ */
+/// Returns the prime factors of n in ascending order, with multiplicity, by trial division.
+///
+/// Time complexity is O(n^(1/2))
+pub fn factorize(mut n: i64) -> Vec<i64> {
+    let mut factors = vec![];
+    let mut p = 2;
+    while p * p <= n {
+        while n % p == 0 {
+            n /= p;
+            factors.push(p);
+        }
+        p += 1;
+    }
+    if n > 1 {
+        factors.push(n);
+    }
+    return factors;
+}
+/* }}} */
+
+
+/* fast_factorize {{{ */
+/// Returns the prime factors of n in ascending order, with multiplicity, by Pollard's rho algorithm.
+///
+/// Time complexity is O(n^(1/4))
+pub fn fast_factorize(n: i64) -> Vec<i64> {
+    let mut factors = vec![];
+    let mut st = vec![];
+    st.push(n);
+    while let Some(top) = st.pop() {
+        if top == 1 {
+            continue;
+        }
+        if fast_is_prime(top) {
+            factors.push(top);
+            continue;
+        }
+        let factor = find_factor(top);
+        st.push(factor);
+        st.push(top / factor);
+    }
+    factors.sort();
+    return factors;
+}
+/* }}} */
+
+
+
+/* LinearSieve {{{ */
+/// Smallest prime factor of every number up to `n` by the linear sieve,
+/// which crosses out each composite exactly once, from its smallest prime factor.
+#[derive(Clone, Debug)]
+pub struct LinearSieve {
+    pub n: usize,
+    /// Smallest prime factor of each number, with `spf[0] = spf[1] = 0`.
+    pub spf: Vec<usize>,
+    pub primes: Vec<usize>,
+}
+
+impl LinearSieve {
+    /// Time complexity is O(n)
+    pub fn new(n: usize) -> Self {
+        let mut spf = vec![0; n + 1];
+        let mut primes = vec![];
+        for i in 2..=n {
+            if spf[i] == 0 {
+                spf[i] = i;
+                primes.push(i);
+            }
+            for &p in &primes {
+                if p > spf[i] || i * p > n {
+                    break;
+                }
+                spf[i * p] = p;
+            }
+        }
+        return Self {n, spf, primes};
+    }
+
+    pub fn is_prime(&self, x: usize) -> bool {
+        return x >= 2 && self.spf[x] == x;
+    }
+
+    /// Returns the prime factors of `x` in ascending order as `(prime, exponent)`.
+    ///
+    /// Time complexity is O(log x)
+    pub fn factorize(&self, mut x: usize) -> Vec<(usize, u32)> {
+        assert!(1 <= x && x <= self.n);
+        let mut res: Vec<(usize, u32)> = vec![];
+        while x > 1 {
+            let p = self.spf[x];
+            match res.last_mut() {
+                Some((q, e)) if *q == p => *e += 1,
+                _ => res.push((p, 1)),
+            }
+            x /= p;
+        }
+        return res;
+    }
+
+    /// Returns the divisors of `x` in ascending order.
+    ///
+    /// Time complexity is O(d(x) log d(x))
+    pub fn divisors(&self, x: usize) -> Vec<usize> {
+        let mut res = vec![1];
+        for (p, e) in self.factorize(x) {
+            let len = res.len();
+            let mut pk = 1;
+            for _ in 0..e {
+                pk *= p;
+                for i in 0..len {
+                    res.push(res[i] * pk);
+                }
+            }
+        }
+        res.sort_unstable();
+        return res;
+    }
+
+    /// Returns Euler's totient φ(x) for each `x` in `0..=n`, with φ(0) = 0.
+    ///
+    /// Time complexity is O(n)
+    pub fn totient(&self) -> Vec<usize> {
+        let mut phi = vec![0; self.n + 1];
+        if self.n >= 1 {
+            phi[1] = 1;
+        }
+        for x in 2..=self.n {
+            let p = self.spf[x];
+            let m = x / p;
+            phi[x] = if self.spf[m] == p { phi[m] * p } else { phi[m] * (p - 1) };
+        }
+        return phi;
+    }
+
+    /// Returns the Möbius function μ(x) for each `x` in `0..=n`, with μ(0) = 0.
+    ///
+    /// Time complexity is O(n)
+    pub fn mobius(&self) -> Vec<i32> {
+        let mut mu = vec![0; self.n + 1];
+        if self.n >= 1 {
+            mu[1] = 1;
+        }
+        for x in 2..=self.n {
+            let p = self.spf[x];
+            let m = x / p;
+            mu[x] = if self.spf[m] == p { 0 } else { -mu[m] };
+        }
+        return mu;
+    }
+}
+/* }}} */
+
+
+/* segmented_sieve {{{ */
+/// Returns the primes in `[l, r)` by sieving the window with the primes up to √r.
+///
+/// Time complexity is O((r - l) log log r + √r)
+pub fn segmented_sieve(l: u64, r: u64) -> Vec<u64> {
+    if r <= l {
+        return vec![];
+    }
+    let mut is_prime = vec![true; (r - l) as usize];
+    for p in eratosthenes(isqrt(r - 1)) {
+        let start = (p * p).max(l + (p - l % p) % p);
+        for j in (start..r).step_by(p as usize) {
+            is_prime[(j - l) as usize] = false;
+        }
+    }
+    return (l..r).filter(|&x| x >= 2 && is_prime[(x - l) as usize]).collect();
+}
+/* }}} */
+
+
+/* isqrt {{{ */
+/// Returns ⌊√n⌋, correcting the floating point estimate.
+fn isqrt(n: u64) -> u64 {
+    let mut x = (n as f64).sqrt() as u64;
+    while x.checked_mul(x).map_or(true, |s| s > n) {
+        x -= 1;
+    }
+    while (x + 1).checked_mul(x + 1).map_or(false, |s| s <= n) {
+        x += 1;
+    }
+    return x;
+}
+/* }}} */
+
+
+/* lucy {{{ */
+/// Returns `(small, large)` where `small[v]` and `large[i]` are the sums of `value(p)` over the primes `p` up to `v` and `n / i`,
+/// for a completely multiplicative `value` whose sum over `2..=v` is `prefix(v)`, by Lucy_Hedgehog's algorithm.
+/// Each prime `p` up to √n removes the numbers whose smallest prime factor is `p`.
+///
+/// Time complexity is O(n^(3/4))
+fn lucy<T>(n: u64, prefix: impl Fn(u64) -> T, value: impl Fn(u64) -> T) -> (Vec<T>, Vec<T>)
+where
+    T: Copy + PartialEq + std::ops::Sub<Output = T> + std::ops::Mul<Output = T>,
+{
+    let r = isqrt(n);
+    let mut small = (0..=r).map(&prefix).collect::<Vec<_>>();
+    // `large[0]` is unused.
+    let mut large = vec![prefix(0)];
+    large.extend((1..=r).map(|i| prefix(n / i)));
+    for p in 2..=r {
+        if small[p as usize] == small[p as usize - 1] {
+            continue;
+        }
+        let below = small[p as usize - 1];
+        let fp = value(p);
+        let p2 = p * p;
+        for i in 1..=r.min(n / p2) {
+            let ip = i * p;
+            let x = if ip <= r { large[ip as usize] } else { small[(n / ip) as usize] };
+            large[i as usize] = large[i as usize] - fp * (x - below);
+        }
+        for v in (p2..=r).rev() {
+            small[v as usize] = small[v as usize] - fp * (small[(v / p) as usize] - below);
+        }
+    }
+    return (small, large);
+}
+/* }}} */
+
+
+/* prime_pi {{{ */
+/// Returns the number of primes up to `n`.
+///
+/// Time complexity is O(n^(3/4))
+pub fn prime_pi(n: u64) -> u64 {
+    if n < 2 {
+        return 0;
+    }
+    let (_, large) = lucy(n, |v| v.saturating_sub(1), |_| 1);
+    return large[1];
+}
+/* }}} */
+
+
+/* prime_sum {{{ */
+/// Returns the sum of the primes up to `n`.
+///
+/// Time complexity is O(n^(3/4))
+pub fn prime_sum(n: u64) -> u128 {
+    if n < 2 {
+        return 0;
+    }
+    let (_, large) = lucy(n, |v| (v as u128 * (v as u128 + 1) / 2).saturating_sub(1), |p| p as u128);
+    return large[1];
+}
+/* }}} */
+
+
+/* prime_sum_mod {{{ */
+/// Returns the sum of the primes up to `n` modulo `M`.
+///
+/// Time complexity is O(n^(3/4))
+pub fn prime_sum_mod<M: ac_library::Modulus>(n: u64) -> ac_library::StaticModInt<M> {
+    type Mint<M> = ac_library::StaticModInt<M>;
+    if n < 2 {
+        return Mint::raw(0);
+    }
+    let prefix = |v: u64| {
+        let (a, b) = if v & 1 == 0 { (v / 2, v + 1) } else { (v, v / 2 + 1) };
+        Mint::<M>::new(a) * Mint::new(b) - Mint::new(1)
+    };
+    let (_, large) = lucy(n, prefix, Mint::new);
+    return large[1];
+}
+/* }}} */
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn modular_arithmetic() {
+        let m = 1_000_000_007;
+        assert_eq!(modmul(1 << 40, 1 << 40, m), ((1i128 << 80) % m as i128) as i64);
+        assert_eq!(modpow(2, 10, m), 1024);
+        assert_eq!(modpow(3, m - 1, m), 1);
+        for a in 1..100 {
+            assert_eq!(modmul(a, modinv(a, m), m), 1);
+            assert_eq!(a * modinv(a, 101) % 101, 1);
+        }
+    }
+
+    #[test]
+    fn primality() {
+        let primes = eratosthenes(100);
+        assert_eq!(primes.len(), 25);
+        assert_eq!(&primes[..5], &[2, 3, 5, 7, 11]);
+        for n in 0..=100 {
+            let expected = primes.contains(&(n as u64));
+            assert_eq!(is_prime(n), expected, "{}", n);
+            assert_eq!(fast_is_prime(n), expected, "{}", n);
+        }
+        assert!(fast_is_prime(998_244_353));
+        assert!(fast_is_prime(1_000_000_000_000_000_003));
+        assert!(!fast_is_prime(1_000_000_007 * 998_244_353));
+    }
+
+    #[test]
+    fn factorization() {
+        assert_eq!(factorize(1), vec![]);
+        assert_eq!(factorize(360), vec![2, 2, 2, 3, 3, 5]);
+        assert_eq!(factorize(97), vec![97]);
+        for n in 1..300 {
+            assert_eq!(fast_factorize(n), factorize(n));
+        }
+        assert_eq!(fast_factorize(1_000_000_007 * 998_244_353), vec![998_244_353, 1_000_000_007]);
+        let n = 1_000_000_007 * 998_244_353;
+        let p = find_factor(n);
+        assert!(p == 1_000_000_007 || p == 998_244_353);
+    }
+
+    #[test]
+    fn linear_sieve() {
+        let n = 2000;
+        let sieve = LinearSieve::new(n);
+        assert_eq!(sieve.primes, eratosthenes(n as u64).iter().map(|&p| p as usize).collect::<Vec<_>>());
+        let phi = sieve.totient();
+        let mu = sieve.mobius();
+        assert_eq!(&phi[..7], &[0, 1, 1, 2, 2, 4, 2]);
+        assert_eq!(&mu[..7], &[0, 1, -1, -1, 0, -1, 1]);
+        for x in 1..=n {
+            assert_eq!(sieve.is_prime(x), is_prime(x as i64));
+            let factors = sieve.factorize(x);
+            let flat = factors.iter().flat_map(|&(p, e)| (0..e).map(move |_| p as i64)).collect::<Vec<_>>();
+            assert_eq!(flat, factorize(x as i64));
+            let divisors = sieve.divisors(x);
+            assert_eq!(divisors, (1..=x).filter(|d| x % d == 0).collect::<Vec<_>>());
+            assert_eq!(phi[x], (1..=x).filter(|&k| num::integer::gcd(k, x) == 1).count());
+            let square_free = factors.iter().all(|&(_, e)| e == 1);
+            assert_eq!(mu[x], if square_free { (-1_i32).pow(factors.len() as u32) } else { 0 });
+        }
+        assert_eq!(LinearSieve::new(1).totient(), vec![0, 1]);
+        assert_eq!(LinearSieve::new(0).mobius(), vec![0]);
+    }
+
+    #[test]
+    fn segmented_sieve() {
+        for (l, r) in [(0, 100), (0, 2), (2, 3), (90, 98), (1000, 1000), (5000, 4000), (999_000, 1_001_000)] {
+            let expected = eratosthenes(r.max(1)).into_iter().filter(|&p| l <= p && p < r).collect::<Vec<_>>();
+            assert_eq!(super::segmented_sieve(l, r), expected, "{} {}", l, r);
+        }
+        let l = 1_000_000_000_000;
+        let primes = super::segmented_sieve(l, l + 100_000);
+        assert!(primes.iter().all(|&p| fast_is_prime(p as i64)));
+        assert_eq!(primes.len(), (l..l + 100_000).filter(|&x| fast_is_prime(x as i64)).count());
+        assert_eq!(primes[0], 1_000_000_000_039);
+    }
+
+    #[test]
+    fn isqrt() {
+        for n in 0..10_000 {
+            let r = super::isqrt(n);
+            assert!(r * r <= n && n < (r + 1) * (r + 1), "{}", n);
+        }
+        assert_eq!(super::isqrt(u64::MAX), u32::MAX as u64);
+        assert_eq!(super::isqrt(u32::MAX as u64 * u32::MAX as u64), u32::MAX as u64);
+        assert_eq!(super::isqrt(u32::MAX as u64 * u32::MAX as u64 - 1), u32::MAX as u64 - 1);
+    }
+
+    #[test]
+    fn prime_counting() {
+        let primes = eratosthenes(100_000);
+        for n in (0..2000).chain([9999, 10_000, 65_536, 99_999, 100_000]) {
+            let below = primes.iter().filter(|&&p| p <= n);
+            assert_eq!(prime_pi(n), below.clone().count() as u64, "{}", n);
+            assert_eq!(prime_sum(n), below.clone().map(|&p| p as u128).sum::<u128>(), "{}", n);
+            assert_eq!(prime_sum_mod::<ac_library::Mod998244353>(n).val() as u128, below.map(|&p| p as u128).sum::<u128>() % 998_244_353);
+        }
+        assert_eq!(prime_pi(1_000_000_000), 50_847_534);
+        assert_eq!(prime_pi(10_000_000_000), 455_052_511);
+        assert_eq!(prime_sum(1_000_000_000), 24_739_512_092_254_535);
+        assert_eq!(prime_sum_mod::<ac_library::Mod1000000007>(1_000_000_000).val() as u128, 24_739_512_092_254_535 % 1_000_000_007);
+    }
+}
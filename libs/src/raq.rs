@@ -0,0 +1,105 @@
+
+/* RAQ (Range Add Query) {{{ */
+/// Array supporting range add and point get.
+pub struct RAQ<T> {
+    n: usize,
+    data: Vec<T>,
+}
+
+impl<T: Clone + std::ops::Add + num::traits::Zero> RAQ<T> {
+    pub fn new(n: usize) -> Self {
+        let n = n.next_power_of_two();
+        Self {
+            n,
+            data: vec![T::zero(); n * 2 - 1],
+        }
+    }
+
+    /// Returns the value at `i`.
+    ///
+    /// Time complexity is O(log n)
+    pub fn get(&self, i: usize) -> T {
+        let mut i = i + self.n - 1;
+        let mut value = self.data[i].clone();
+        while i > 0 {
+            i = (i - 1) / 2;
+            value = value + self.data[i].clone();
+        }
+        value
+    }
+
+    /// Adds `value` to the value at `i`.
+    ///
+    /// Time complexity is O(1)
+    pub fn add(&mut self, i: usize, value: T) {
+        self.data[i + self.n - 1] = self.data[i + self.n - 1].clone() + value;
+    }
+
+    /// Adds `value` to every value in `range`.
+    ///
+    /// Time complexity is O(log n)
+    pub fn add_range<R: std::ops::RangeBounds<usize>>(&mut self, range: R, value: T) {
+        let start = match range.start_bound() {
+            std::ops::Bound::Included(&s) => s,
+            std::ops::Bound::Excluded(&s) => s + 1,
+            std::ops::Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            std::ops::Bound::Included(&e) => e + 1,
+            std::ops::Bound::Excluded(&e) => e,
+            std::ops::Bound::Unbounded => self.n,
+        };
+        let mut l = start + self.n - 1;
+        let mut r = end + self.n - 1;
+        while l < r {
+            if l & 1 == 0 {
+                self.data[l] = self.data[l].clone() + value.clone();
+            }
+            if r & 1 == 0 {
+                self.data[r - 1] = self.data[r - 1].clone() + value.clone();
+            }
+            l /= 2;
+            r = (r - 1) / 2;
+        }
+    }
+}
+/* }}} */
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_brute_force() {
+        let n = 13;
+        let mut raq = RAQ::new(n);
+        let mut brute = vec![0i64; n];
+        let mut x = 7;
+        for step in 0..200 {
+            x = (x * 37 + 11) % 1009;
+            let l = x % n;
+            let r = l + (x / n) % (n - l + 1);
+            let value = x as i64 - 500;
+            if step % 3 == 0 {
+                raq.add(l, value);
+                brute[l] += value;
+            } else {
+                raq.add_range(l..r, value);
+                (l..r).for_each(|i| brute[i] += value);
+            }
+            for i in 0..n {
+                assert_eq!(raq.get(i), brute[i]);
+            }
+        }
+    }
+
+    #[test]
+    fn range_bounds() {
+        let mut raq = RAQ::new(5);
+        raq.add_range(.., 1);
+        raq.add_range(1..=3, 10);
+        raq.add_range(3.., 100);
+        assert_eq!((0..5).map(|i| raq.get(i)).collect::<Vec<i64>>(), vec![1, 11, 11, 111, 101]);
+    }
+}
@@ -0,0 +1,158 @@
+
+/* Rerooting {{{ */
+/// Tree DP computed for every choice of root at once.
+///
+/// Like `ac_library::Monoid`, `S` with `identity` and `merge` is a commutative monoid merging the children of a vertex,
+/// but the methods take `&self` so that an implementation can hold edge weights or vertex values.
+/// Implement the four methods and call `solve`.
+pub trait Rerooting {
+
+    type S: Clone;
+
+
+    fn identity(&self) -> Self::S;
+    fn merge(&self, a: &Self::S, b: &Self::S) -> Self::S;
+    /// Lifts the value of the subtree of `child` over the edge to `parent`.
+    /// Both orientations of every edge are used, since which endpoint is the parent depends on the root.
+    fn add_edge(&self, x: &Self::S, child: usize, parent: usize) -> Self::S;
+    /// Returns the value of the subtree of `v` from the merged values of its lifted children.
+    fn add_vertex(&self, x: &Self::S, v: usize) -> Self::S;
+
+
+    /* solve {{{ */
+    /// Returns the DP value of the whole tree rooted at each vertex.
+    ///
+    /// Time complexity is O(V) calls of the four methods
+    fn solve(&self, tree: &super::tree::Tree) -> Vec<Self::S> {
+        let n = tree.n;
+        // down[v] is the value of the subtree of v in `tree`.
+        let mut down = vec![self.identity(); n];
+        for &v in tree.order.iter().rev() {
+            let mut acc = self.identity();
+            for &c in &tree.children[v] {
+                acc = self.merge(&acc, &self.add_edge(&down[c], c, v));
+            }
+            down[v] = self.add_vertex(&acc, v);
+        }
+
+        // up[v] is the lifted value of everything outside the subtree of v, seen from v.
+        let mut up = vec![self.identity(); n];
+        let mut result = vec![self.identity(); n];
+        for &v in &tree.order {
+            let children = &tree.children[v];
+            let lifted = children.iter().map(|&c| self.add_edge(&down[c], c, v)).collect::<Vec<_>>();
+            // suffix[i] merges lifted[i..] together with up[v].
+            let mut suffix = vec![up[v].clone(); children.len() + 1];
+            for i in (0..children.len()).rev() {
+                suffix[i] = self.merge(&lifted[i], &suffix[i + 1]);
+            }
+            result[v] = self.add_vertex(&suffix[0], v);
+            let mut prefix = self.identity();
+            for (i, &c) in children.iter().enumerate() {
+                let rest = self.add_vertex(&self.merge(&prefix, &suffix[i + 1]), v);
+                up[c] = self.add_edge(&rest, v, c);
+                prefix = self.merge(&prefix, &lifted[i]);
+            }
+        }
+        return result;
+    }
+    /* }}} */
+
+}
+/* }}} */
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tree::Tree;
+    use rand::{Rng, SeedableRng};
+
+    /// Sum of distances to all vertices, as (number of vertices, sum of distances).
+    struct DistSum;
+
+    impl Rerooting for DistSum {
+        type S = (usize, usize);
+
+        fn identity(&self) -> Self::S {
+            (0, 0)
+        }
+
+        fn merge(&self, a: &Self::S, b: &Self::S) -> Self::S {
+            (a.0 + b.0, a.1 + b.1)
+        }
+
+        fn add_edge(&self, &(cnt, sum): &Self::S, _: usize, _: usize) -> Self::S {
+            (cnt, sum + cnt)
+        }
+
+        fn add_vertex(&self, &(cnt, sum): &Self::S, _: usize) -> Self::S {
+            (cnt + 1, sum)
+        }
+    }
+
+    /// Farthest distance with edge weights, where the weight of an edge is stored at its endpoint farther from vertex 0.
+    struct Eccentricity<'a> {
+        parent: &'a [Option<usize>],
+        weight: &'a [i64],
+    }
+
+    impl Rerooting for Eccentricity<'_> {
+        type S = i64;
+
+        fn identity(&self) -> i64 {
+            0
+        }
+
+        fn merge(&self, a: &i64, b: &i64) -> i64 {
+            *a.max(b)
+        }
+
+        fn add_edge(&self, x: &i64, child: usize, parent: usize) -> i64 {
+            let w = if self.parent[child] == Some(parent) { self.weight[child] } else { self.weight[parent] };
+            x + w
+        }
+
+        fn add_vertex(&self, x: &i64, _: usize) -> i64 {
+            *x
+        }
+    }
+
+    #[test]
+    fn random() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+        for _ in 0..30 {
+            let n = rng.gen_range(1..30);
+            let parent = (0..n).map(|v| if v == 0 { None } else { Some(rng.gen_range(0..v)) }).collect::<Vec<_>>();
+            let weight = (0..n).map(|_| rng.gen_range(1..10)).collect::<Vec<i64>>();
+            let t = Tree::from_parents(&parent);
+            let sums = DistSum.solve(&t.reroot(rng.gen_range(0..n)));
+            let ecc = Eccentricity {parent: &parent, weight: &weight}.solve(&t);
+            for v in 0..n {
+                assert_eq!(sums[v], (n, (0..n).map(|u| t.dist(u, v)).sum()));
+                let weighted = |u: usize| {
+                    let w = t.lca(u, v);
+                    let up = |mut x: usize| {
+                        let mut d = 0;
+                        while x != w {
+                            d += weight[x];
+                            x = parent[x].unwrap();
+                        }
+                        d
+                    };
+                    up(u) + up(v)
+                };
+                assert_eq!(ecc[v], (0..n).map(weighted).max().unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn deep() {
+        let n = 200_000;
+        let parent = (0..n).map(|v: usize| v.checked_sub(1)).collect::<Vec<_>>();
+        let sums = DistSum.solve(&Tree::from_parents(&parent));
+        assert_eq!(sums[0].1, n * (n - 1) / 2);
+        assert_eq!(sums[n / 2].1, (n / 2) * (n / 2 + 1) / 2 + (n / 2 - 1) * (n / 2) / 2);
+    }
+}
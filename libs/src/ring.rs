@@ -0,0 +1,62 @@
+
+/* Ring {{{ */
+/// Positions 0..len arranged on a circle.
+pub struct Ring {
+    len: usize,
+}
+
+impl Ring {
+    pub fn new(len: usize) -> Self {
+        Self { len }
+    }
+
+    /// Returns the number of steps from `from` to `to` in the increasing direction.
+    pub fn dist_right(&self, from: usize, mut to: usize) -> usize {
+        if from > to {
+            to += self.len;
+        }
+        to - from
+    }
+
+    /// Returns the number of steps from `from` to `to` in the decreasing direction.
+    pub fn dist_left(&self, from: usize, to: usize) -> usize {
+        self.len - self.dist_right(from, to)
+    }
+
+    /// Returns whether `subject` lies on the way from `from` to `to` in the increasing direction.
+    pub fn contains_right(&self, from: usize, to: usize, subject: usize) -> bool {
+        self.dist_right(from, subject) <= self.dist_right(from, to)
+    }
+
+    /// Returns whether `subject` lies on the way from `from` to `to` in the decreasing direction.
+    pub fn contains_left(&self, from: usize, to: usize, subject: usize) -> bool {
+        self.dist_left(from, subject) <= self.dist_left(from, to)
+    }
+}
+/* }}} */
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dist() {
+        let ring = Ring::new(10);
+        assert_eq!(ring.dist_right(2, 5), 3);
+        assert_eq!(ring.dist_right(8, 1), 3);
+        assert_eq!(ring.dist_left(2, 5), 7);
+        assert_eq!(ring.dist_left(8, 1), 7);
+        assert_eq!(ring.dist_right(4, 4), 0);
+    }
+
+    #[test]
+    fn contains() {
+        let ring = Ring::new(10);
+        assert!(ring.contains_right(8, 2, 9));
+        assert!(ring.contains_right(8, 2, 0));
+        assert!(!ring.contains_right(8, 2, 5));
+        assert!(ring.contains_left(2, 8, 0));
+        assert!(!ring.contains_left(2, 8, 5));
+    }
+}
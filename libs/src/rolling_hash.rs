@@ -2,16 +2,9 @@
 /* RollingHash {{{ */
 use ac_library::{ModInt998244353 as Mint1, ModInt1000000007 as Mint2};
 
-#[derive(Copy, Clone, Debug, Hash)]
-struct RHash (Mint1, Mint2);
-
-impl PartialEq for RHash {
-    fn eq(&self, other: &Self) -> bool {
-        return self.0.val() == other.0.val() && self.1.val() == other.1.val();
-    }
-}
-
-impl Eq for RHash {}
+/// Hash value of a string, a pair of residues modulo two primes.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub struct RHash (pub Mint1, pub Mint2);
 
 impl PartialOrd for RHash {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
@@ -26,17 +19,18 @@ impl Ord for RHash {
 }
 
 
+/// Prefix hashes of a string for O(1) substring hashes.
 #[derive(Clone, Debug)]
-struct RollingHash {
+pub struct RollingHash {
     hash: Vec<RHash>,
     power: Vec<RHash>,
 }
 
-#[allow(dead_code)]
 impl RollingHash {
     const BASE: i64 = 31;
 
-    fn new(s: &String) -> Self {
+    /// Time complexity is O(n)
+    pub fn new(s: &str) -> Self {
         let s = s.chars().collect::<Vec<char>>();
         let n = s.len();
         let mut hash = vec![RHash(Mint1::from(0), Mint2::from(0)); n + 1];
@@ -52,7 +46,10 @@ impl RollingHash {
         Self {hash, power}
     }
 
-    fn get_hash(&self, l: usize, r: usize) -> RHash {
+    /// Returns the hash of `s[l..r]`.
+    ///
+    /// Time complexity is O(1)
+    pub fn get_hash(&self, l: usize, r: usize) -> RHash {
         RHash(
             self.hash[r].0 - self.hash[l].0 * self.power[r - l].0,
             self.hash[r].1 - self.hash[l].1 * self.power[r - l].1
@@ -60,3 +57,23 @@ impl RollingHash {
     }
 }
 /* }}} */
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equal_substrings_have_equal_hashes() {
+        let s = "abracadabra";
+        let rh = RollingHash::new(s);
+        let n = s.len();
+        for (l1, r1, l2, r2) in itertools::iproduct!(0..n, 0..=n, 0..n, 0..=n) {
+            if l1 > r1 || l2 > r2 {
+                continue;
+            }
+            assert_eq!(rh.get_hash(l1, r1) == rh.get_hash(l2, r2), s[l1..r1] == s[l2..r2]);
+        }
+        assert_eq!(rh.get_hash(0, 4), RollingHash::new("abra").get_hash(0, 4));
+    }
+}
@@ -0,0 +1,53 @@
+
+/* Timer {{{ */
+/// Wall-clock timer with a time limit in milliseconds.
+pub struct Timer {
+    instant: std::time::Instant,
+    timeout_ms: u128,
+}
+
+impl Timer {
+    pub fn new(timeout_ms: u128) -> Self {
+        Self {
+            instant: std::time::Instant::now(),
+            timeout_ms,
+        }
+    }
+
+    /// Returns the milliseconds since creation or the last `reset`.
+    pub fn elapsed(&self) -> u128 {
+        self.instant.elapsed().as_millis()
+    }
+
+    pub fn is_timeout(&self) -> bool {
+        self.elapsed() >= self.timeout_ms
+    }
+
+    pub fn reset(&mut self) {
+        self.instant = std::time::Instant::now();
+    }
+
+    pub fn set_timeout(&mut self, timeout_ms: u128) {
+        self.timeout_ms = timeout_ms;
+    }
+}
+/* }}} */
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timeout() {
+        let mut timer = Timer::new(20);
+        assert!(!timer.is_timeout());
+        std::thread::sleep(std::time::Duration::from_millis(30));
+        assert!(timer.elapsed() >= 20);
+        assert!(timer.is_timeout());
+        timer.reset();
+        assert!(!timer.is_timeout());
+        timer.set_timeout(0);
+        assert!(timer.is_timeout());
+    }
+}
@@ -0,0 +1,333 @@
+
+/* Tree {{{ */
+/// Rooted tree with parents, depths, subtree sizes and binary lifting for ancestor queries.
+#[derive(Clone, Debug)]
+pub struct Tree {
+    pub n: usize,
+    pub root: usize,
+    /// Parent of each vertex, or `None` for the root.
+    pub parent: Vec<Option<usize>>,
+    pub children: Vec<Vec<usize>>,
+    /// Number of edges from the root.
+    pub depth: Vec<usize>,
+    /// Number of vertices in the subtree of each vertex, including itself.
+    pub size: Vec<usize>,
+    /// Vertices in BFS order from the root, so every parent comes before its children.
+    pub order: Vec<usize>,
+    /// `ancestor[j][v]` is the `2^j`-th ancestor of `v`, or the root if it is too shallow.
+    ancestor: Vec<Vec<usize>>,
+}
+
+impl Tree {
+    /* from_adjacency {{{ */
+    /// Roots the tree given by undirected adjacency lists at `root`.
+    ///
+    /// Time complexity is O(V log V)
+    pub fn from_adjacency(adj: &[Vec<usize>], root: usize) -> Self {
+        let n = adj.len();
+        let mut parent = vec![None; n];
+        let mut children = vec![vec![]; n];
+        let mut depth = vec![0; n];
+        let mut order = vec![root];
+        let mut visited = vec![false; n];
+        visited[root] = true;
+        let mut i = 0;
+        while i < order.len() {
+            let v = order[i];
+            for &to in &adj[v] {
+                if !visited[to] {
+                    visited[to] = true;
+                    parent[to] = Some(v);
+                    children[v].push(to);
+                    depth[to] = depth[v] + 1;
+                    order.push(to);
+                }
+            }
+            i += 1;
+        }
+        assert_eq!(order.len(), n, "the graph is not connected");
+
+        let mut size = vec![1; n];
+        for &v in order.iter().rev() {
+            if let Some(p) = parent[v] {
+                size[p] += size[v];
+            }
+        }
+
+        let mut ancestor = vec![(0..n).map(|v| parent[v].unwrap_or(root)).collect::<Vec<_>>()];
+        while 1 << ancestor.len() < n {
+            let last = ancestor.last().unwrap();
+            let next = (0..n).map(|v| last[last[v]]).collect();
+            ancestor.push(next);
+        }
+        return Self {n, root, parent, children, depth, size, order, ancestor};
+    }
+    /* }}} */
+
+
+    /* from_graph {{{ */
+    /// Roots the tree given by `g` at `root`, treating each edge as undirected.
+    ///
+    /// Time complexity is O(V log V)
+    pub fn from_graph<W>(g: &super::graph::Graph<W>, root: usize) -> Self {
+        let mut adj = vec![vec![]; g.n];
+        for e in &g.edges {
+            adj[e.from].push(e.to);
+            adj[e.to].push(e.from);
+        }
+        // Edges added in both directions appear twice, which is harmless.
+        return Self::from_adjacency(&adj, root);
+    }
+    /* }}} */
+
+
+    /* from_parents {{{ */
+    /// Builds the tree where `parent[v]` is the parent of `v` and the root is the only vertex with `None`.
+    ///
+    /// Time complexity is O(V log V)
+    pub fn from_parents(parent: &[Option<usize>]) -> Self {
+        let mut adj = vec![vec![]; parent.len()];
+        let mut root = None;
+        for (v, &p) in parent.iter().enumerate() {
+            match p {
+                Some(p) => {
+                    adj[p].push(v);
+                    adj[v].push(p);
+                }
+                None => {
+                    assert!(root.is_none(), "there are several roots");
+                    root = Some(v);
+                }
+            }
+        }
+        return Self::from_adjacency(&adj, root.expect("there is no root"));
+    }
+    /* }}} */
+
+
+    /* reroot {{{ */
+    /// Returns the same tree rooted at `root`.
+    ///
+    /// Time complexity is O(V log V)
+    pub fn reroot(&self, root: usize) -> Self {
+        let mut adj = self.children.clone();
+        for v in 0..self.n {
+            if let Some(p) = self.parent[v] {
+                adj[v].push(p);
+            }
+        }
+        return Self::from_adjacency(&adj, root);
+    }
+    /* }}} */
+
+
+    /* kth_ancestor {{{ */
+    /// Returns the ancestor `k` edges above `v`, or `None` if `v` is shallower than `k`.
+    ///
+    /// Time complexity is O(log V)
+    pub fn kth_ancestor(&self, mut v: usize, k: usize) -> Option<usize> {
+        if k > self.depth[v] {
+            return None;
+        }
+        for j in 0..self.ancestor.len() {
+            if k >> j & 1 == 1 {
+                v = self.ancestor[j][v];
+            }
+        }
+        return Some(v);
+    }
+    /* }}} */
+
+
+    /* lca {{{ */
+    /// Returns the lowest common ancestor of `u` and `v`.
+    ///
+    /// Time complexity is O(log V)
+    pub fn lca(&self, u: usize, v: usize) -> usize {
+        let (mut u, mut v) = if self.depth[u] < self.depth[v] { (v, u) } else { (u, v) };
+        u = self.kth_ancestor(u, self.depth[u] - self.depth[v]).unwrap();
+        if u == v {
+            return u;
+        }
+        for j in (0..self.ancestor.len()).rev() {
+            if self.ancestor[j][u] != self.ancestor[j][v] {
+                u = self.ancestor[j][u];
+                v = self.ancestor[j][v];
+            }
+        }
+        return self.ancestor[0][u];
+    }
+    /* }}} */
+
+
+    /* dist {{{ */
+    /// Returns the number of edges on the path between `u` and `v`.
+    ///
+    /// Time complexity is O(log V)
+    pub fn dist(&self, u: usize, v: usize) -> usize {
+        return self.depth[u] + self.depth[v] - 2 * self.depth[self.lca(u, v)];
+    }
+    /* }}} */
+
+
+    /* jump {{{ */
+    /// Returns the vertex `d` edges away from `u` on the path from `u` to `v`,
+    /// or `None` if the path is shorter than `d`.
+    ///
+    /// Time complexity is O(log V)
+    pub fn jump(&self, u: usize, v: usize, d: usize) -> Option<usize> {
+        let w = self.lca(u, v);
+        let du = self.depth[u] - self.depth[w];
+        let dv = self.depth[v] - self.depth[w];
+        if d <= du {
+            return self.kth_ancestor(u, d);
+        }
+        if d <= du + dv {
+            return self.kth_ancestor(v, du + dv - d);
+        }
+        return None;
+    }
+    /* }}} */
+}
+/* }}} */
+
+
+/* EulerTourLca {{{ */
+/// Lowest common ancestor in O(1) per query with a sparse table over the Euler tour of a `Tree`.
+#[derive(Clone, Debug)]
+pub struct EulerTourLca {
+    /// Index of the first occurrence of each vertex in the tour.
+    first: Vec<usize>,
+    /// `table[j][i]` is the shallowest vertex among `tour[i..i + 2^j]`.
+    table: Vec<Vec<usize>>,
+    depth: Vec<usize>,
+}
+
+impl EulerTourLca {
+    /// Builds the sparse table over the Euler tour of `tree`.
+    ///
+    /// Time complexity is O(V log V)
+    pub fn new(tree: &Tree) -> Self {
+        let mut tour = Vec::with_capacity(2 * tree.n - 1);
+        let mut first = vec![0; tree.n];
+        // Each vertex is written on entry and again after returning from each child.
+        let mut stack = vec![(tree.root, 0)];
+        while let Some(top) = stack.last_mut() {
+            let (v, i) = *top;
+            if i == 0 {
+                first[v] = tour.len();
+            }
+            tour.push(v);
+            if i < tree.children[v].len() {
+                top.1 += 1;
+                stack.push((tree.children[v][i], 0));
+            } else {
+                stack.pop();
+            }
+        }
+
+        let shallower = |a: usize, b: usize| if tree.depth[a] <= tree.depth[b] { a } else { b };
+        let mut table = vec![tour];
+        while 1 << table.len() <= table[0].len() {
+            let last = table.last().unwrap();
+            let half = 1 << (table.len() - 1);
+            let next = (0..last.len() - half).map(|i| shallower(last[i], last[i + half])).collect();
+            table.push(next);
+        }
+        return Self {first, table, depth: tree.depth.clone()};
+    }
+
+    /// Returns the lowest common ancestor of `u` and `v`.
+    ///
+    /// Time complexity is O(1)
+    pub fn lca(&self, u: usize, v: usize) -> usize {
+        let (l, r) = if self.first[u] <= self.first[v] { (self.first[u], self.first[v] + 1) } else { (self.first[v], self.first[u] + 1) };
+        let j = (usize::BITS - 1 - (r - l).leading_zeros()) as usize;
+        let (a, b) = (self.table[j][l], self.table[j][r - (1 << j)]);
+        return if self.depth[a] <= self.depth[b] { a } else { b };
+    }
+}
+/* }}} */
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+
+    //       0
+    //      / \
+    //     1   2
+    //    / \   \
+    //   3   4   5
+    //       |
+    //       6
+    fn sample() -> Tree {
+        return Tree::from_parents(&[None, Some(0), Some(0), Some(1), Some(1), Some(2), Some(4)]);
+    }
+
+    #[test]
+    fn structure() {
+        let t = sample();
+        assert_eq!(t.root, 0);
+        assert_eq!(t.depth, vec![0, 1, 1, 2, 2, 2, 3]);
+        assert_eq!(t.size, vec![7, 4, 2, 1, 2, 1, 1]);
+        assert_eq!(t.parent[6], Some(4));
+        let r = t.reroot(4);
+        assert_eq!(r.parent[1], Some(4));
+        assert_eq!(r.parent[0], Some(1));
+        assert_eq!(r.size[1], 5);
+        assert_eq!(r.depth[5], 4);
+    }
+
+    #[test]
+    fn from_graph() {
+        let mut g = crate::graph::Graph::new(4);
+        g.add_edge(0, 1, 5);
+        g.add_edge(2, 1, 5);
+        g.add_edge(1, 2, 5);
+        g.add_edge(3, 2, 5);
+        let t = Tree::from_graph(&g, 2);
+        assert_eq!(t.parent, vec![Some(1), Some(2), None, Some(2)]);
+        assert_eq!(t.order.len(), 4);
+    }
+
+    #[test]
+    fn queries() {
+        let t = sample();
+        assert_eq!(t.lca(3, 6), 1);
+        assert_eq!(t.lca(6, 5), 0);
+        assert_eq!(t.lca(4, 6), 4);
+        assert_eq!(t.kth_ancestor(6, 2), Some(1));
+        assert_eq!(t.kth_ancestor(6, 4), None);
+        assert_eq!(t.dist(6, 5), 5);
+        let path = (0..=5).map(|d| t.jump(6, 5, d).unwrap()).collect::<Vec<_>>();
+        assert_eq!(path, vec![6, 4, 1, 0, 2, 5]);
+        assert_eq!(t.jump(6, 5, 6), None);
+        assert_eq!(t.jump(3, 3, 0), Some(3));
+    }
+
+    #[test]
+    fn euler_tour_lca() {
+        let mut rng = rand::rngs::SmallRng::seed_from_u64(0);
+        for _ in 0..30 {
+            let n = rng.gen_range(1..40);
+            let parent = (0..n).map(|v| if v == 0 { None } else { Some(rng.gen_range(0..v)) }).collect::<Vec<_>>();
+            let t = Tree::from_parents(&parent).reroot(rng.gen_range(0..n));
+            let et = EulerTourLca::new(&t);
+            for u in 0..n {
+                for v in 0..n {
+                    let naive = {
+                        let (mut a, mut b) = (u, v);
+                        while a != b {
+                            if t.depth[a] >= t.depth[b] { a = t.parent[a].unwrap() } else { b = t.parent[b].unwrap() }
+                        }
+                        a
+                    };
+                    assert_eq!(t.lca(u, v), naive);
+                    assert_eq!(et.lca(u, v), naive);
+                }
+            }
+        }
+    }
+}
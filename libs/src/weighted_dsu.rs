@@ -1,13 +1,14 @@
 
 /* WeightedUnionFind {{{ */
-struct WeightedDsu {
+/// Disjoint set union that also keeps the weight difference between elements of a set.
+pub struct WeightedDsu {
     parent: Vec<usize>,
     rank: Vec<usize>,
     diff_weight: Vec<i64>, // 親との重みの差
 }
 
 impl WeightedDsu {
-    fn new(n: usize) -> Self {
+    pub fn new(n: usize) -> Self {
         Self {
             parent: (0..n).collect(),
             rank: vec![0; n],
@@ -15,7 +16,7 @@ impl WeightedDsu {
         }
     }
 
-    fn leader(&mut self, x: usize) -> usize {
+    pub fn leader(&mut self, x: usize) -> usize {
         if self.parent[x] != x {
             let p = self.parent[x];
             self.parent[x] = self.leader(p);
@@ -24,11 +25,13 @@ impl WeightedDsu {
         self.parent[x]
     }
 
-    fn same(&mut self, x: usize, y: usize) -> bool {
+    pub fn same(&mut self, x: usize, y: usize) -> bool {
         return self.leader(x) == self.leader(y);
     }
 
-    fn merge(&mut self, x: usize, y: usize, w: i64) -> bool {
+    /// weight(y) - weight(x) = w となるように併合する
+    /// 既に同じ集合にあれば何もせず false を返す
+    pub fn merge(&mut self, x: usize, y: usize, w: i64) -> bool {
         let mut root_x = self.leader(x);
         let mut root_y = self.leader(y);
         if root_x == root_y {
@@ -59,3 +62,26 @@ impl WeightedDsu {
     }
 }
 /* }}} */
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff() {
+        let mut dsu = WeightedDsu::new(5);
+        assert!(dsu.merge(0, 1, 3));
+        assert!(dsu.merge(2, 1, -2));
+        assert!(dsu.merge(3, 4, 10));
+        assert_eq!(dsu.diff(0, 1), Some(3));
+        assert_eq!(dsu.diff(0, 2), Some(5));
+        assert_eq!(dsu.diff(2, 0), Some(-5));
+        assert_eq!(dsu.diff(0, 3), None);
+
+        assert!(dsu.merge(4, 2, 1));
+        assert_eq!(dsu.diff(3, 0), Some(6));
+        assert!(!dsu.merge(0, 3, 100));
+        assert!(dsu.same(1, 4));
+    }
+}
@@ -0,0 +1,117 @@
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/* Set {{{ */
+/// Hash of a set, updated in O(1) per toggled element.
+/// Equal sets have equal hashes regardless of the order of updates.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Set<T: Hash> {
+    val: u64,
+    _marker: std::marker::PhantomData<T>,
+}
+
+impl<T: Hash> Set<T> {
+
+    pub fn new() -> Self {
+        return Self {val: 0, _marker: std::marker::PhantomData};
+    }
+
+    /// Inserts `x` if it is absent, and removes it otherwise.
+    pub fn flip(&mut self, x: &T) {
+        let mut hasher = DefaultHasher::new();
+        x.hash(&mut hasher);
+        let hash = hasher.finish();
+        self.val ^= hash;
+    }
+}
+/* }}} */
+
+
+/* MultiSet {{{ */
+/// Hash of a multiset, updated in O(1) per inserted or removed element.
+/// Equal multisets have equal hashes regardless of the order of updates.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct MultiSet<T: Hash> {
+    val: u64,
+    _marker: std::marker::PhantomData<T>,
+}
+
+impl<T: Hash> MultiSet<T> {
+    pub fn new() -> Self {
+        return Self {val: 0, _marker: std::marker::PhantomData};
+    }
+
+    pub fn insert(&mut self, x: T) {
+        let mut hasher = DefaultHasher::new();
+        x.hash(&mut hasher);
+        self.val = self.val.wrapping_add(hasher.finish());
+    }
+
+    pub fn remove(&mut self, x: &T) {
+        let mut hasher = DefaultHasher::new();
+        x.hash(&mut hasher);
+        self.val = self.val.wrapping_sub(hasher.finish());
+    }
+
+    /// Inserts every element of `other`.
+    pub fn extend(&mut self, other: &Self) {
+        self.val = self.val.wrapping_add(other.val);
+    }
+
+    /// Removes every element of `other`, which must be a sub-multiset of `self`.
+    pub fn subtract(&mut self, other: &Self) {
+        self.val = self.val.wrapping_sub(other.val);
+    }
+}
+/* }}} */
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set() {
+        let mut a = Set::new();
+        let mut b = Set::new();
+        for x in [1, 2, 3] {
+            a.flip(&x);
+        }
+        for x in [3, 5, 1, 2, 5] {
+            b.flip(&x);
+        }
+        assert!(a == b);
+        a.flip(&2);
+        assert!(a != b);
+        a.flip(&2);
+        a.flip(&1);
+        a.flip(&2);
+        a.flip(&3);
+        assert!(a == Set::new());
+    }
+
+    #[test]
+    fn multiset() {
+        let mut a = MultiSet::new();
+        let mut b = MultiSet::new();
+        for x in [1, 1, 2] {
+            a.insert(x);
+        }
+        for x in [2, 1, 3, 1] {
+            b.insert(x);
+        }
+        assert!(a != b);
+        b.remove(&3);
+        assert!(a == b);
+        a.remove(&1);
+        assert!(a != b);
+
+        let mut c = MultiSet::new();
+        c.insert(1);
+        a.extend(&c);
+        assert!(a == b);
+        a.subtract(&b);
+        assert!(a == MultiSet::new());
+    }
+}